@@ -0,0 +1,7 @@
+-- vim: ft=pgsql
+
+ALTER TABLE guild
+ADD COLUMN invite_poll_decision_rule varchar NOT NULL DEFAULT 'veto', -- DecisionRule
+ADD CONSTRAINT guild_invite_poll_decision_rule_is_valid CHECK (
+    invite_poll_decision_rule ~ '^(veto|majority|supermajority:\d+|max-opposition:\d+)$'
+);
@@ -0,0 +1,5 @@
+-- vim: ft=pgsql
+
+-- new enum values cannot be used in the transaction that adds them, hence the
+-- view is updated in a separate migration
+ALTER TYPE invite_poll_vote ADD VALUE 'abstain';
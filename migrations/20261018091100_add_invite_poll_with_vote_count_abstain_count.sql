@@ -0,0 +1,14 @@
+-- vim: ft=pgsql
+
+CREATE OR REPLACE VIEW invite_poll_with_vote_count AS
+SELECT
+    ip.*,
+    count(ipvs.user_id) FILTER (WHERE ipvs.vote = 'yes') AS yes_count,
+    count(ipvs.user_id) FILTER (WHERE ipvs.vote = 'no') AS no_count,
+    count(ipvs.user_id) FILTER (WHERE ipvs.vote = 'abstain') AS abstain_count
+FROM invite_poll AS ip
+LEFT JOIN invite_poll_vote_submission AS ipvs ON ipvs.invite_poll_id = ip.id
+GROUP BY ip.id;
+
+ALTER TABLE guild
+ADD COLUMN abstentions_count_toward_quorum boolean NOT NULL DEFAULT false;
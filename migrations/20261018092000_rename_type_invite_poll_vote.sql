@@ -0,0 +1,4 @@
+-- vim: ft=pgsql
+
+-- votes are shared by all yes/no polls
+ALTER TYPE invite_poll_vote RENAME TO poll_vote;
@@ -0,0 +1,32 @@
+-- vim: ft=pgsql
+
+CREATE TYPE kick_poll_outcome AS ENUM ('kick', 'keep');
+
+CREATE TABLE kick_poll (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(), -- KickPollId
+    guild_id varchar NOT NULL REFERENCES guild (id), -- GuildId
+    initiator varchar NOT NULL, -- UserId
+    target varchar NOT NULL, -- UserId
+    channel_id varchar, -- ChannelId
+    message_id varchar, -- MessageId
+    outcome kick_poll_outcome,
+    message varchar,
+    ends_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE TRIGGER kick_poll_update_updated_at
+BEFORE UPDATE ON kick_poll
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+ALTER TABLE guild
+ADD COLUMN kick_poll_quorum real NOT NULL DEFAULT 0.5,
+ADD COLUMN kick_poll_decision_rule varchar NOT NULL DEFAULT 'majority', -- DecisionRule
+ADD CONSTRAINT guild_kick_poll_quorum_is_percentage CHECK (
+    kick_poll_quorum >= 0 AND kick_poll_quorum <= 1
+),
+ADD CONSTRAINT guild_kick_poll_decision_rule_is_valid CHECK (
+    kick_poll_decision_rule ~ '^(veto|majority|supermajority:\d+|max-opposition:\d+)$'
+);
@@ -0,0 +1,15 @@
+-- vim: ft=pgsql
+
+CREATE TABLE kick_poll_vote_submission (
+    kick_poll_id uuid NOT NULL REFERENCES kick_poll (id), -- KickPollId
+    user_id varchar NOT NULL, -- UserId
+    vote poll_vote NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (kick_poll_id, user_id)
+);
+
+CREATE TRIGGER kick_poll_vote_submission_update_updated_at
+BEFORE UPDATE ON kick_poll_vote_submission
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,11 @@
+-- vim: ft=pgsql
+
+CREATE VIEW kick_poll_with_vote_count AS
+SELECT
+    kp.*,
+    count(kpvs.user_id) FILTER (WHERE kpvs.vote = 'yes') AS yes_count,
+    count(kpvs.user_id) FILTER (WHERE kpvs.vote = 'no') AS no_count,
+    count(kpvs.user_id) FILTER (WHERE kpvs.vote = 'abstain') AS abstain_count
+FROM kick_poll AS kp
+LEFT JOIN kick_poll_vote_submission AS kpvs ON kpvs.kick_poll_id = kp.id
+GROUP BY kp.id;
@@ -0,0 +1,32 @@
+-- vim: ft=pgsql
+
+CREATE TYPE community_poll_outcome AS ENUM ('passed', 'rejected');
+
+CREATE TABLE community_poll (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(), -- CommunityPollId
+    guild_id varchar NOT NULL REFERENCES guild (id), -- GuildId
+    author varchar NOT NULL, -- UserId
+    question varchar NOT NULL,
+    description varchar,
+    quorum real NOT NULL,
+    decision_rule varchar NOT NULL, -- DecisionRule
+    channel_id varchar, -- ChannelId
+    message_id varchar, -- MessageId
+    outcome community_poll_outcome,
+    message varchar,
+    ends_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+
+    CONSTRAINT community_poll_quorum_is_percentage CHECK (
+        quorum >= 0 AND quorum <= 1
+    ),
+    CONSTRAINT community_poll_decision_rule_is_valid CHECK (
+        decision_rule ~ '^(veto|majority|supermajority:\d+|max-opposition:\d+)$'
+    )
+);
+
+CREATE TRIGGER community_poll_update_updated_at
+BEFORE UPDATE ON community_poll
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
@@ -0,0 +1,15 @@
+-- vim: ft=pgsql
+
+CREATE TABLE community_poll_vote_submission (
+    community_poll_id uuid NOT NULL REFERENCES community_poll (id), -- CommunityPollId
+    user_id varchar NOT NULL, -- UserId
+    vote poll_vote NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (community_poll_id, user_id)
+);
+
+CREATE TRIGGER community_poll_vote_submission_update_updated_at
+BEFORE UPDATE ON community_poll_vote_submission
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
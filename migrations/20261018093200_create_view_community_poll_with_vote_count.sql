@@ -0,0 +1,11 @@
+-- vim: ft=pgsql
+
+CREATE VIEW community_poll_with_vote_count AS
+SELECT
+    cp.*,
+    count(cpvs.user_id) FILTER (WHERE cpvs.vote = 'yes') AS yes_count,
+    count(cpvs.user_id) FILTER (WHERE cpvs.vote = 'no') AS no_count,
+    count(cpvs.user_id) FILTER (WHERE cpvs.vote = 'abstain') AS abstain_count
+FROM community_poll AS cp
+LEFT JOIN community_poll_vote_submission AS cpvs ON cpvs.community_poll_id = cp.id
+GROUP BY cp.id;
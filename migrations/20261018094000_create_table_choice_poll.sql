@@ -0,0 +1,28 @@
+-- vim: ft=pgsql
+
+CREATE TYPE choice_poll_outcome AS ENUM ('decided', 'tied', 'no_votes');
+
+CREATE TABLE choice_poll (
+    id uuid PRIMARY KEY DEFAULT gen_random_uuid(), -- ChoicePollId
+    guild_id varchar NOT NULL REFERENCES guild (id), -- GuildId
+    author varchar NOT NULL, -- UserId
+    question varchar NOT NULL,
+    description varchar,
+    channel_id varchar, -- ChannelId
+    message_id varchar, -- MessageId
+    outcome choice_poll_outcome,
+    winner smallint, -- position of the winning option
+    message varchar,
+    ends_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+
+    CONSTRAINT choice_poll_winner_is_decided CHECK (
+        (outcome = 'decided') = (winner IS NOT NULL)
+    )
+);
+
+CREATE TRIGGER choice_poll_update_updated_at
+BEFORE UPDATE ON choice_poll
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
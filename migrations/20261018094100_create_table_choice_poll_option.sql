@@ -0,0 +1,19 @@
+-- vim: ft=pgsql
+
+CREATE TABLE choice_poll_option (
+    choice_poll_id uuid NOT NULL REFERENCES choice_poll (id), -- ChoicePollId
+    position smallint NOT NULL,
+    label varchar NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (choice_poll_id, position),
+
+    CONSTRAINT choice_poll_option_position_is_valid CHECK (
+        position >= 0 AND position < 25
+    )
+);
+
+CREATE TRIGGER choice_poll_option_update_updated_at
+BEFORE UPDATE ON choice_poll_option
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
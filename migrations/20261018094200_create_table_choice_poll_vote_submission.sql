@@ -0,0 +1,16 @@
+-- vim: ft=pgsql
+
+CREATE TABLE choice_poll_vote_submission (
+    choice_poll_id uuid NOT NULL REFERENCES choice_poll (id), -- ChoicePollId
+    user_id varchar NOT NULL, -- UserId
+    position smallint NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (choice_poll_id, user_id),
+    FOREIGN KEY (choice_poll_id, position) REFERENCES choice_poll_option (choice_poll_id, position)
+);
+
+CREATE TRIGGER choice_poll_vote_submission_update_updated_at
+BEFORE UPDATE ON choice_poll_vote_submission
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
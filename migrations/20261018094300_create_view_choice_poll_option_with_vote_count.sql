@@ -0,0 +1,10 @@
+-- vim: ft=pgsql
+
+CREATE VIEW choice_poll_option_with_vote_count AS
+SELECT
+    cpo.*,
+    count(cpvs.user_id) AS vote_count
+FROM choice_poll_option AS cpo
+LEFT JOIN choice_poll_vote_submission AS cpvs
+    ON cpvs.choice_poll_id = cpo.choice_poll_id AND cpvs.position = cpo.position
+GROUP BY cpo.choice_poll_id, cpo.position;
@@ -0,0 +1,6 @@
+-- vim: ft=pgsql
+
+CREATE TYPE choice_poll_mode AS ENUM ('single', 'ranked');
+
+ALTER TABLE choice_poll
+ADD COLUMN mode choice_poll_mode NOT NULL DEFAULT 'single';
@@ -0,0 +1,33 @@
+-- vim: ft=pgsql
+
+CREATE TABLE choice_poll_ranking (
+    choice_poll_id uuid NOT NULL REFERENCES choice_poll (id), -- ChoicePollId
+    user_id varchar NOT NULL, -- UserId
+    rank smallint NOT NULL, -- 0 is the most preferred
+    position smallint NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (choice_poll_id, user_id, rank),
+    UNIQUE (choice_poll_id, user_id, position),
+    FOREIGN KEY (choice_poll_id, position) REFERENCES choice_poll_option (choice_poll_id, position)
+);
+
+CREATE TRIGGER choice_poll_ranking_update_updated_at
+BEFORE UPDATE ON choice_poll_ranking
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- ranked polls count first preferences
+CREATE OR REPLACE VIEW choice_poll_option_with_vote_count AS
+SELECT
+    cpo.*,
+    (
+        SELECT count(cpvs.user_id)
+        FROM choice_poll_vote_submission AS cpvs
+        WHERE cpvs.choice_poll_id = cpo.choice_poll_id AND cpvs.position = cpo.position
+    ) + (
+        SELECT count(cpr.user_id)
+        FROM choice_poll_ranking AS cpr
+        WHERE cpr.choice_poll_id = cpo.choice_poll_id AND cpr.position = cpo.position AND cpr.rank = 0
+    ) AS vote_count
+FROM choice_poll_option AS cpo;
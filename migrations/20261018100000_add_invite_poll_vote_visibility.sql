@@ -0,0 +1,22 @@
+-- vim: ft=pgsql
+
+CREATE TYPE vote_visibility AS ENUM ('secret', 'after_close', 'public');
+
+ALTER TABLE guild
+ADD COLUMN vote_visibility vote_visibility NOT NULL DEFAULT 'secret';
+
+ALTER TABLE invite_poll
+ADD COLUMN vote_visibility vote_visibility NOT NULL DEFAULT 'secret';
+
+-- `ip.*` is expanded when the view is created, the view has to be recreated to include the new column
+DROP VIEW invite_poll_with_vote_count;
+
+CREATE VIEW invite_poll_with_vote_count AS
+SELECT
+    ip.*,
+    count(ipvs.user_id) FILTER (WHERE ipvs.vote = 'yes') AS yes_count,
+    count(ipvs.user_id) FILTER (WHERE ipvs.vote = 'no') AS no_count,
+    count(ipvs.user_id) FILTER (WHERE ipvs.vote = 'abstain') AS abstain_count
+FROM invite_poll AS ip
+LEFT JOIN invite_poll_vote_submission AS ipvs ON ipvs.invite_poll_id = ip.id
+GROUP BY ip.id;
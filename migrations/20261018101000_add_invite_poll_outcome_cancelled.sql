@@ -0,0 +1,3 @@
+-- vim: ft=pgsql
+
+ALTER TYPE invite_poll_outcome ADD VALUE 'cancelled';
@@ -0,0 +1,15 @@
+-- vim: ft=pgsql
+
+CREATE TYPE invite_poll_audit_action AS ENUM ('close', 'extend', 'reopen');
+
+CREATE TABLE invite_poll_audit_entry (
+    id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    invite_poll_id uuid NOT NULL REFERENCES invite_poll (id), -- InvitePollId
+    user_id varchar NOT NULL, -- UserId
+    action invite_poll_audit_action NOT NULL,
+    details varchar,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX invite_poll_audit_entry_invite_poll_id_idx
+ON invite_poll_audit_entry (invite_poll_id);
@@ -0,0 +1,18 @@
+-- vim: ft=pgsql
+
+-- only keep the most recent open poll for each invitee
+UPDATE invite_poll AS ip
+SET outcome = 'cancelled', message = 'superseded by a newer poll'
+WHERE ip.outcome IS NULL AND EXISTS (
+    SELECT 1
+    FROM invite_poll AS newer
+    WHERE
+        newer.guild_id = ip.guild_id
+        AND newer.invitee = ip.invitee
+        AND newer.outcome IS NULL
+        AND newer.created_at > ip.created_at
+);
+
+CREATE UNIQUE INDEX invite_poll_open_invitee_key
+ON invite_poll (guild_id, invitee)
+WHERE outcome IS NULL;
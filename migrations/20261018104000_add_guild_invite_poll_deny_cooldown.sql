@@ -0,0 +1,5 @@
+-- vim: ft=pgsql
+
+-- how long after a denied poll the same user cannot be proposed again, zero disables the cooldown
+ALTER TABLE guild
+ADD COLUMN invite_poll_deny_cooldown interval NOT NULL DEFAULT '0';
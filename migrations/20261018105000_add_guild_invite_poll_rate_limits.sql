@@ -0,0 +1,7 @@
+-- vim: ft=pgsql
+
+-- zero disables the respective limit
+ALTER TABLE guild
+ADD COLUMN invite_poll_max_open_per_inviter integer NOT NULL DEFAULT 0,
+ADD COLUMN invite_poll_max_per_inviter integer NOT NULL DEFAULT 0,
+ADD COLUMN invite_poll_rate_limit_window interval NOT NULL DEFAULT '0';
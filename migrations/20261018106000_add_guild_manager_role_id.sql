@@ -0,0 +1,4 @@
+-- vim: ft=pgsql
+
+ALTER TABLE guild
+ADD COLUMN manager_role_id varchar; -- RoleId
@@ -0,0 +1,6 @@
+-- vim: ft=pgsql
+
+-- empty arrays allow every member
+ALTER TABLE guild
+ADD COLUMN voter_role_ids varchar[] NOT NULL DEFAULT '{}', -- RoleId[]
+ADD COLUMN proposer_role_ids varchar[] NOT NULL DEFAULT '{}'; -- RoleId[]
@@ -0,0 +1,4 @@
+-- vim: ft=pgsql
+
+ALTER TABLE guild
+ADD COLUMN min_days_to_vote integer NOT NULL DEFAULT 0;
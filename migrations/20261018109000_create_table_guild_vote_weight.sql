@@ -0,0 +1,29 @@
+-- vim: ft=pgsql
+
+CREATE TABLE guild_vote_weight (
+    guild_id varchar NOT NULL REFERENCES guild (id), -- GuildId
+    role_id varchar NOT NULL, -- RoleId
+    weight integer NOT NULL CHECK (weight > 0),
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, role_id)
+);
+
+CREATE TRIGGER guild_vote_weight_update_updated_at
+BEFORE UPDATE ON guild_vote_weight
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- the weight of the voter when the vote was submitted
+ALTER TABLE invite_poll_vote_submission
+ADD COLUMN weight integer NOT NULL DEFAULT 1 CHECK (weight > 0);
+
+CREATE OR REPLACE VIEW invite_poll_with_vote_count AS
+SELECT
+    ip.*,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'yes'), 0) AS yes_count,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'no'), 0) AS no_count,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'abstain'), 0) AS abstain_count
+FROM invite_poll AS ip
+LEFT JOIN invite_poll_vote_submission AS ipvs ON ipvs.invite_poll_id = ip.id
+GROUP BY ip.id;
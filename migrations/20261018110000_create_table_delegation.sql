@@ -0,0 +1,15 @@
+-- vim: ft=pgsql
+
+CREATE TABLE delegation (
+    guild_id varchar NOT NULL REFERENCES guild (id), -- GuildId
+    delegator varchar NOT NULL, -- UserId
+    delegate varchar NOT NULL CHECK (delegate <> delegator), -- UserId
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, delegator)
+);
+
+CREATE TRIGGER delegation_update_updated_at
+BEFORE UPDATE ON delegation
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
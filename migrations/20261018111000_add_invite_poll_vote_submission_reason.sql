@@ -0,0 +1,5 @@
+-- vim: ft=pgsql
+
+-- the optional reason given by the voter when voting no
+ALTER TABLE invite_poll_vote_submission
+ADD COLUMN reason text;
@@ -0,0 +1,5 @@
+-- vim: ft=pgsql
+
+-- whether invite polls close as soon as the remaining votes cannot change their outcome
+ALTER TABLE guild
+ADD COLUMN invite_poll_early_close boolean NOT NULL DEFAULT false;
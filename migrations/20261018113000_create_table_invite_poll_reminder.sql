@@ -0,0 +1,14 @@
+-- vim: ft=pgsql
+
+-- how long before the end of invite polls reminders are posted
+ALTER TABLE guild
+ADD COLUMN invite_poll_reminders interval[] NOT NULL DEFAULT '{}',
+ADD COLUMN reminder_role_id varchar; -- RoleId
+
+-- the reminders already posted, so that each one is only posted once
+CREATE TABLE invite_poll_reminder (
+    invite_poll_id uuid NOT NULL REFERENCES invite_poll (id), -- InvitePollId
+    remind_before interval NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (invite_poll_id, remind_before)
+);
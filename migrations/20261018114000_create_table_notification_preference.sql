@@ -0,0 +1,23 @@
+-- vim: ft=pgsql
+
+CREATE TABLE notification_preference (
+    guild_id varchar NOT NULL REFERENCES guild (id), -- GuildId
+    user_id varchar NOT NULL, -- UserId
+    -- whether the member is sent a direct message when an invite poll they have not voted on is
+    -- about to end
+    vote_nudges boolean NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TRIGGER notification_preference_update_updated_at
+BEFORE UPDATE ON notification_preference
+FOR EACH ROW
+EXECUTE FUNCTION update_updated_at();
+
+-- the invite polls whose voters were already nudged, so that they are only nudged once
+CREATE TABLE invite_poll_nudge (
+    invite_poll_id uuid PRIMARY KEY REFERENCES invite_poll (id), -- InvitePollId
+    created_at timestamptz NOT NULL DEFAULT now()
+);
@@ -0,0 +1,5 @@
+-- vim: ft=pgsql
+
+-- the channel where the results of invite polls are announced
+ALTER TABLE guild
+ADD COLUMN results_channel_id varchar; -- ChannelId
@@ -0,0 +1,28 @@
+-- vim: ft=pgsql
+
+-- the channel where audit events are mirrored
+ALTER TABLE guild
+ADD COLUMN log_channel_id varchar; -- ChannelId
+
+CREATE TYPE audit_event_kind AS ENUM (
+    'config_changed',
+    'poll_created',
+    'poll_cancelled',
+    'poll_force_closed',
+    'invite_generated',
+    'delivery_failed'
+);
+
+CREATE TABLE audit_event (
+    id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    guild_id varchar NOT NULL, -- GuildId
+    kind audit_event_kind NOT NULL,
+    -- the user who performed the action, if any
+    user_id varchar, -- UserId
+    invite_poll_id uuid REFERENCES invite_poll (id), -- InvitePollId
+    details text NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX audit_event_guild_id_idx
+ON audit_event (guild_id);
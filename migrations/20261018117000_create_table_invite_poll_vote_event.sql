@@ -0,0 +1,31 @@
+-- vim: ft=pgsql
+
+-- Append-only history of every vote cast or changed on an invite poll.
+CREATE TABLE invite_poll_vote_event (
+    id bigint PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    invite_poll_id uuid NOT NULL REFERENCES invite_poll (id), -- InvitePollId
+    user_id varchar NOT NULL, -- UserId
+    vote poll_vote NOT NULL,
+    -- the vote the user had before this event, NULL on their first vote
+    previous_vote poll_vote,
+    weight integer NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX invite_poll_vote_event_invite_poll_id_idx
+ON invite_poll_vote_event (invite_poll_id);
+
+-- Trigger function that rejects any change to the records of an append-only
+-- table.
+CREATE FUNCTION reject_append_only_change()
+RETURNS trigger
+LANGUAGE plpgsql
+AS $$
+	BEGIN
+		RAISE EXCEPTION '% is append-only', TG_TABLE_NAME;
+	END;
+$$;
+
+CREATE TRIGGER invite_poll_vote_event_reject_append_only_change
+BEFORE UPDATE OR DELETE ON invite_poll_vote_event
+FOR EACH ROW EXECUTE FUNCTION reject_append_only_change();
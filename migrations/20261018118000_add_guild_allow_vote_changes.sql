@@ -0,0 +1,5 @@
+-- vim: ft=pgsql
+
+-- whether members can change their vote on an open invite poll
+ALTER TABLE guild
+ADD COLUMN allow_vote_changes boolean NOT NULL DEFAULT true;
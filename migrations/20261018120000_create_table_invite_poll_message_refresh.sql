@@ -0,0 +1,17 @@
+-- vim: ft=pgsql
+
+-- invite polls whose message is stale and has to be re-rendered by the bot
+CREATE TABLE invite_poll_message_refresh (
+    invite_poll_id uuid PRIMARY KEY REFERENCES invite_poll (id), -- InvitePollId
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+-- the duplicate polls cancelled when the open invitee index was created still show their vote
+-- buttons
+INSERT INTO invite_poll_message_refresh (invite_poll_id)
+SELECT ip.id
+FROM invite_poll AS ip
+WHERE
+    ip.outcome = 'cancelled'
+    AND ip.message = 'superseded by a newer poll'
+    AND ip.message_id IS NOT NULL;
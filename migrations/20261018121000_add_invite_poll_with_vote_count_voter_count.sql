@@ -0,0 +1,14 @@
+-- vim: ft=pgsql
+
+-- the vote counts are sums of the voters' weights, `voter_count` is the number of voters
+CREATE OR REPLACE VIEW invite_poll_with_vote_count AS
+SELECT
+    ip.*,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'yes'), 0) AS yes_count,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'no'), 0) AS no_count,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'abstain'), 0) AS abstain_count,
+    count(ipvs.user_id) FILTER (WHERE ipvs.inherited_from IS NOT NULL) AS delegated_count,
+    count(ipvs.user_id) AS voter_count
+FROM invite_poll AS ip
+LEFT JOIN invite_poll_vote_submission AS ipvs ON ipvs.invite_poll_id = ip.id
+GROUP BY ip.id;
@@ -0,0 +1,6 @@
+-- vim: ft=pgsql
+
+-- the new values cannot be used in the transaction adding them, the audit entries of the invite
+-- polls are moved in the next migration
+ALTER TYPE audit_event_kind ADD VALUE 'poll_extended';
+ALTER TYPE audit_event_kind ADD VALUE 'poll_reopened';
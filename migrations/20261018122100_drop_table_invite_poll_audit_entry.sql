@@ -0,0 +1,32 @@
+-- vim: ft=pgsql
+
+-- the administrative actions on invite polls are recorded as audit events, the forced closes
+-- already were
+INSERT INTO audit_event (guild_id, kind, user_id, invite_poll_id, details, created_at)
+SELECT
+    ip.guild_id,
+    CASE ipae.action
+        WHEN 'close' THEN 'poll_force_closed'::audit_event_kind
+        WHEN 'extend' THEN 'poll_extended'::audit_event_kind
+        WHEN 'reopen' THEN 'poll_reopened'::audit_event_kind
+    END,
+    ipae.user_id,
+    ipae.invite_poll_id,
+    CASE ipae.action
+        WHEN 'close' THEN 'invite poll for <@' || ip.invitee || '>'
+        WHEN 'extend' THEN 'extended by ' || COALESCE(ipae.details, 'an unknown duration')
+        WHEN 'reopen' THEN 'reopened for at least ' || COALESCE(ipae.details, 'an unknown duration')
+    END,
+    ipae.created_at
+FROM invite_poll_audit_entry AS ipae
+JOIN invite_poll AS ip ON ip.id = ipae.invite_poll_id
+WHERE
+    ipae.action <> 'close'
+    OR NOT EXISTS (
+        SELECT 1
+        FROM audit_event AS ae
+        WHERE ae.invite_poll_id = ipae.invite_poll_id AND ae.kind = 'poll_force_closed'
+    );
+
+DROP TABLE invite_poll_audit_entry;
+DROP TYPE invite_poll_audit_action;
@@ -0,0 +1,6 @@
+-- vim: ft=pgsql
+
+-- set on the votes inherited through a delegation when the poll closes, the id of the member whose
+-- vote was inherited
+ALTER TABLE invite_poll_vote_event
+ADD COLUMN inherited_from varchar; -- UserId
@@ -0,0 +1,26 @@
+-- vim: ft=pgsql
+
+-- when the poll was closed, `updated_at` no longer tells since reopened polls can be closed again
+ALTER TABLE invite_poll
+ADD COLUMN closed_at timestamptz;
+
+-- closed polls were only updated when they were closed so far
+UPDATE invite_poll
+SET closed_at = updated_at
+WHERE outcome IS NOT NULL;
+
+-- `ip.*` is expanded when the view is created, the view has to be recreated to include the new column
+DROP VIEW invite_poll_with_vote_count;
+
+CREATE VIEW invite_poll_with_vote_count AS
+SELECT
+    ip.*,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'yes'), 0) AS yes_count,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'no'), 0) AS no_count,
+    COALESCE(sum(ipvs.weight) FILTER (WHERE ipvs.vote = 'abstain'), 0) AS abstain_count,
+    count(ipvs.user_id) FILTER (WHERE ipvs.inherited_from IS NOT NULL) AS delegated_count,
+    count(ipvs.user_id) AS voter_count
+FROM invite_poll AS ip
+LEFT JOIN invite_poll_vote_submission AS ipvs ON ipvs.invite_poll_id = ip.id
+GROUP BY ip.id;
+
@@ -0,0 +1,20 @@
+-- vim: ft=pgsql
+
+-- audit events can refer to polls of any kind, which cannot share a foreign key
+CREATE TYPE poll_kind AS ENUM ('invite', 'kick', 'community', 'choice');
+
+ALTER TABLE audit_event
+ADD COLUMN poll_kind poll_kind,
+ADD COLUMN poll_id uuid, -- InvitePollId, KickPollId, CommunityPollId or ChoicePollId
+ADD CONSTRAINT audit_event_poll_check CHECK ((poll_kind IS NULL) = (poll_id IS NULL));
+
+UPDATE audit_event
+SET poll_kind = 'invite', poll_id = invite_poll_id
+WHERE invite_poll_id IS NOT NULL;
+
+ALTER TABLE audit_event
+DROP COLUMN invite_poll_id;
+
+-- the outcomes of the kick polls which decided to kick their target
+ALTER TYPE audit_event_kind ADD VALUE 'member_kicked';
+ALTER TYPE audit_event_kind ADD VALUE 'kick_failed';
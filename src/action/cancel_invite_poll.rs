@@ -0,0 +1,114 @@
+use serenity::{
+    all::ComponentInteraction, async_trait, builder::CreateInteractionResponseMessage,
+    model::prelude::Interaction, prelude::Context,
+};
+
+use crate::{
+    entities::{
+        AuditEvent, AuditEventKind, InvitePollId, InvitePollOutcome, InvitePollWithVoteCount,
+        PollRef,
+    },
+    error::Error,
+    util::serenity::UserId,
+    POOL,
+};
+
+use super::{
+    util::{is_manager, parse_poll_id_field},
+    Action, ParseActionError,
+};
+
+const ACTION_ID: &'static str = "democracy.invite-poll-cancel";
+
+#[derive(Debug)]
+pub struct CancelInvitePoll {
+    interaction: ComponentInteraction,
+    invite_poll_id: InvitePollId,
+    /// Canceller's Id
+    user_id: UserId,
+}
+
+#[async_trait]
+impl Action for CancelInvitePoll {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        // load the poll
+        let mut invite_poll = InvitePollWithVoteCount::find_by_id(pool, &self.invite_poll_id)
+            .await?
+            .ok_or_else(|| Error::InvitePollNotFound(self.invite_poll_id.to_owned()))?;
+
+        // preliminary checks
+        if invite_poll.invite_poll.outcome.is_some() {
+            return Err(Error::InvitePollClosed(self.invite_poll_id.to_owned()));
+        }
+        if *invite_poll.invite_poll.inviter != *self.user_id {
+            let member = self.interaction.member.as_ref();
+            if !is_manager(pool, &invite_poll.invite_poll.guild_id, member).await? {
+                return Err(Error::CannotCancelInvitePoll(self.user_id.clone()));
+            }
+        }
+
+        // cancel the poll, the cancellation is audited along with it
+        let mut transaction = pool.begin().await?;
+        invite_poll
+            .invite_poll
+            .close(
+                &mut *transaction,
+                InvitePollOutcome::Cancelled,
+                Some(format!("cancelled by {}", self.user_id)),
+            )
+            .await?;
+        let event = AuditEvent::create(
+            &mut *transaction,
+            &invite_poll.invite_poll.guild_id,
+            AuditEventKind::PollCancelled,
+            Some(&self.user_id),
+            Some(&PollRef::Invite(self.invite_poll_id.clone())),
+            &format!("invite poll for {}", invite_poll.invite_poll.invitee),
+        )
+        .await?;
+        transaction.commit().await?;
+        invite_poll.load_votes(pool).await?;
+
+        // re-render message
+        let renderer = invite_poll.create_renderer(ctx.clone()).await?;
+        self.interaction
+            .create_response(
+                &ctx.http,
+                serenity::builder::CreateInteractionResponse::UpdateMessage(
+                    renderer.render_create_interaction_response_data(
+                        CreateInteractionResponseMessage::default(),
+                    ),
+                ),
+            )
+            .await?;
+
+        event.mirror(&ctx.http, pool).await;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for CancelInvitePoll {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_message_component()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if interaction.data.custom_id != ACTION_ID {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        let invite_poll_id = parse_poll_id_field::<InvitePollId>(interaction)?;
+
+        let user_id = UserId::from(interaction.user.id);
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            invite_poll_id,
+            user_id,
+        })
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use serenity::{
     all::{CommandInteraction, CommandOptionType},
     async_trait,
@@ -5,26 +7,374 @@ use serenity::{
         CreateCommand, CreateCommandOption, CreateEmbed, CreateInteractionResponse,
         CreateInteractionResponseMessage,
     },
-    model::prelude::{Channel, Interaction},
+    model::prelude::Interaction,
     prelude::Context,
 };
+use sqlx::{postgres::types::PgInterval, Executor, Postgres};
 
 use crate::{
-    entities::Guild,
+    entities::{
+        AuditEvent, AuditEventKind, DecisionRule, Guild, GuildSettingsUpdate, GuildVoteWeight,
+        VoteVisibility,
+    },
     error::Error,
     resolve_option,
     util::{
         colors,
-        serenity::{ChannelId, GuildId},
+        serenity::{ChannelId, GuildId, RoleId},
     },
     POOL,
 };
 
-use super::{Action, ParseActionError};
+use super::{util::is_manager, Action, ParseActionError};
 
 const ACTION_ID: &'static str = "configure";
 const INVITE_CHANNEL_ID_OPTION_NAME: &'static str = "invite-channel";
 const INVITE_POLL_QUORUM_OPTION_NAME: &'static str = "invite-poll-quorum";
+const INVITE_POLL_DECISION_RULE_OPTION_NAME: &'static str = "invite-poll-decision-rule";
+const ABSTENTIONS_COUNT_TOWARD_QUORUM_OPTION_NAME: &'static str = "abstentions-count-toward-quorum";
+const KICK_POLL_QUORUM_OPTION_NAME: &'static str = "kick-poll-quorum";
+const KICK_POLL_DECISION_RULE_OPTION_NAME: &'static str = "kick-poll-decision-rule";
+const VOTE_VISIBILITY_OPTION_NAME: &'static str = "vote-visibility";
+const INVITE_POLL_DENY_COOLDOWN_OPTION_NAME: &'static str = "invite-poll-deny-cooldown";
+const INVITE_POLL_MAX_OPEN_PER_INVITER_OPTION_NAME: &'static str =
+    "invite-poll-max-open-per-inviter";
+const INVITE_POLL_RATE_LIMIT_OPTION_NAME: &'static str = "invite-poll-rate-limit";
+const MANAGER_ROLE_ID_OPTION_NAME: &'static str = "manager-role";
+const VOTER_ROLE_IDS_OPTION_NAME: &'static str = "voter-roles";
+const PROPOSER_ROLE_IDS_OPTION_NAME: &'static str = "proposer-roles";
+const MIN_DAYS_TO_VOTE_OPTION_NAME: &'static str = "min-days-to-vote";
+const VOTE_WEIGHTS_OPTION_NAME: &'static str = "vote-weights";
+const INVITE_POLL_EARLY_CLOSE_OPTION_NAME: &'static str = "invite-poll-early-close";
+const INVITE_POLL_REMINDERS_OPTION_NAME: &'static str = "invite-poll-reminders";
+const REMINDER_ROLE_ID_OPTION_NAME: &'static str = "reminder-role";
+const RESULTS_CHANNEL_ID_OPTION_NAME: &'static str = "results-channel";
+const LOG_CHANNEL_ID_OPTION_NAME: &'static str = "log-channel";
+const ALLOW_VOTE_CHANGES_OPTION_NAME: &'static str = "allow-vote-changes";
+const CLEAR_OPTION_NAME: &'static str = "clear";
+
+/// Parses a list of role mentions or ids, `everyone` clears the list.
+fn parse_role_ids(value: &str) -> Result<Vec<RoleId>, std::num::ParseIntError> {
+    if value.trim().eq_ignore_ascii_case("everyone") {
+        return Ok(Vec::new());
+    }
+
+    value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.trim_start_matches("<@&")
+                .trim_end_matches('>')
+                .parse::<RoleId>()
+        })
+        .collect()
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ParseVoteWeightsError {
+    #[error("expected `<role>=<weight>`, got `{0}`")]
+    MissingSeparator(String),
+
+    #[error("invalid role `{0}`: {1}")]
+    InvalidRole(String, #[source] std::num::ParseIntError),
+
+    #[error("invalid weight `{0}`: {1}")]
+    InvalidWeight(String, #[source] std::num::ParseIntError),
+
+    #[error("weight `{0}` must be between 1 and 100")]
+    WeightOutOfRange(u8),
+}
+
+/// Parses a list of role mentions or ids with their weights (e.g. `@Elder=3 @Member=2`), `none`
+/// clears the list.
+fn parse_vote_weights(value: &str) -> Result<Vec<(RoleId, i32)>, ParseVoteWeightsError> {
+    if value.trim().eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (role_id, weight) = s
+                .split_once('=')
+                .ok_or_else(|| ParseVoteWeightsError::MissingSeparator(s.to_owned()))?;
+            let role_id = role_id
+                .trim_start_matches("<@&")
+                .trim_end_matches('>')
+                .parse::<RoleId>()
+                .map_err(|err| ParseVoteWeightsError::InvalidRole(role_id.to_owned(), err))?;
+            let weight = weight
+                .parse::<u8>()
+                .map_err(|err| ParseVoteWeightsError::InvalidWeight(weight.to_owned(), err))?;
+            if !(1..=100).contains(&weight) {
+                return Err(ParseVoteWeightsError::WeightOutOfRange(weight));
+            }
+
+            Ok((role_id, weight.into()))
+        })
+        .collect()
+}
+
+/// Parses a list of durations (e.g. `24h 1h`), `none` clears the list.
+fn parse_reminders(value: &str) -> Result<Vec<Duration>, humantime::DurationError> {
+    if value.trim().eq_ignore_ascii_case("none") {
+        return Ok(Vec::new());
+    }
+
+    value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(humantime::parse_duration)
+        .collect()
+}
+
+/// An optional setting which can be unset through the `clear` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClearableSetting {
+    ManagerRole,
+    ReminderRole,
+    ResultsChannel,
+    LogChannel,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ParseClearError {
+    #[error("`{0}` cannot be cleared, expected `manager-role`, `reminder-role`, `results-channel` or `log-channel`")]
+    UnknownSetting(String),
+
+    #[error("`{0}` cannot be both set and cleared")]
+    AlsoSet(&'static str),
+}
+
+impl ClearableSetting {
+    fn option_name(&self) -> &'static str {
+        match self {
+            ClearableSetting::ManagerRole => MANAGER_ROLE_ID_OPTION_NAME,
+            ClearableSetting::ReminderRole => REMINDER_ROLE_ID_OPTION_NAME,
+            ClearableSetting::ResultsChannel => RESULTS_CHANNEL_ID_OPTION_NAME,
+            ClearableSetting::LogChannel => LOG_CHANNEL_ID_OPTION_NAME,
+        }
+    }
+
+    /// Unsets the setting, unless it is also being set.
+    fn clear(&self, settings: &mut GuildSettingsUpdate) -> Result<(), ParseClearError> {
+        let previous = match self {
+            ClearableSetting::ManagerRole => settings.manager_role_id.replace(None).is_some(),
+            ClearableSetting::ReminderRole => settings.reminder_role_id.replace(None).is_some(),
+            ClearableSetting::ResultsChannel => settings.results_channel_id.replace(None).is_some(),
+            ClearableSetting::LogChannel => settings.log_channel_id.replace(None).is_some(),
+        };
+
+        if previous {
+            Err(ParseClearError::AlsoSet(self.option_name()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl std::str::FromStr for ClearableSetting {
+    type Err = ParseClearError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            ClearableSetting::ManagerRole,
+            ClearableSetting::ReminderRole,
+            ClearableSetting::ResultsChannel,
+            ClearableSetting::LogChannel,
+        ]
+        .into_iter()
+        .find(|setting| setting.option_name().eq_ignore_ascii_case(s))
+        .ok_or_else(|| ParseClearError::UnknownSetting(s.to_owned()))
+    }
+}
+
+/// Parses a list of setting names (e.g. `manager-role log-channel`) to unset.
+fn parse_cleared_settings(value: &str) -> Result<Vec<ClearableSetting>, ParseClearError> {
+    value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+fn format_role_ids(role_ids: &[RoleId]) -> String {
+    if role_ids.is_empty() {
+        "Everyone".to_owned()
+    } else {
+        role_ids
+            .iter()
+            .map(RoleId::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ParseRateLimitError {
+    #[error("expected `<count>/<duration>`")]
+    MissingSeparator,
+
+    #[error("invalid count: {0}")]
+    InvalidCount(#[source] std::num::ParseIntError),
+
+    #[error("invalid duration: {0}")]
+    InvalidDuration(#[source] humantime::DurationError),
+}
+
+/// Parses a rate limit of the form `<count>/<duration>` (e.g. `3/7d`), `0` disables it.
+fn parse_rate_limit(value: &str) -> Result<(i32, Duration), ParseRateLimitError> {
+    let value = value.trim();
+    if value == "0" {
+        return Ok((0, Duration::ZERO));
+    }
+
+    let (count, window) = value
+        .split_once('/')
+        .ok_or(ParseRateLimitError::MissingSeparator)?;
+    let count = count
+        .trim()
+        .parse::<u16>()
+        .map_err(ParseRateLimitError::InvalidCount)?;
+    let window =
+        humantime::parse_duration(window.trim()).map_err(ParseRateLimitError::InvalidDuration)?;
+
+    Ok((count.into(), window))
+}
+
+/// Formats an interval set through a humantime duration.
+fn format_interval(interval: &PgInterval) -> String {
+    // months are never set since the intervals are created from durations
+    let duration = Duration::from_secs(interval.days.max(0) as u64 * 24 * 60 * 60)
+        + Duration::from_micros(interval.microseconds.max(0) as u64);
+
+    if duration.is_zero() {
+        "None".to_owned()
+    } else {
+        humantime::format_duration(duration).to_string()
+    }
+}
+
+/// Loads the vote weights of a guild, formatted for the settings embed.
+async fn find_vote_weights<'c, E>(executor: E, guild_id: &GuildId) -> Result<Vec<String>, Error>
+where
+    E: Executor<'c, Database = Postgres>,
+{
+    let res = GuildVoteWeight::find_by_guild_id(executor, guild_id)
+        .await?
+        .iter()
+        .map(|weight| format!("{}: {}", weight.role_id, weight.weight))
+        .collect();
+
+    Ok(res)
+}
+
+/// The name and formatted value of each setting of a guild.
+fn settings_fields(guild: &Guild, vote_weights: &[String]) -> Vec<(&'static str, String)> {
+    let yes_no = |value: bool| if value { "Yes" } else { "No" }.to_owned();
+
+    vec![
+        ("Invite Channel", guild.invite_channel_id.to_string()),
+        (
+            "Required Votes",
+            format!("{:.0}%", guild.invite_poll_quorum * 100.0),
+        ),
+        (
+            "Decision Rule",
+            format!("`{}`", guild.invite_poll_decision_rule),
+        ),
+        (
+            "Abstentions Count Toward Quorum",
+            yes_no(guild.abstentions_count_toward_quorum),
+        ),
+        (
+            "Kick Poll Required Votes",
+            format!("{:.0}%", guild.kick_poll_quorum * 100.0),
+        ),
+        (
+            "Kick Poll Decision Rule",
+            format!("`{}`", guild.kick_poll_decision_rule),
+        ),
+        ("Vote Visibility", guild.vote_visibility.to_string()),
+        (
+            "Deny Cooldown",
+            format_interval(&guild.invite_poll_deny_cooldown),
+        ),
+        (
+            "Max Open Polls Per Inviter",
+            match guild.invite_poll_max_open_per_inviter {
+                0 => "Unlimited".to_owned(),
+                max => max.to_string(),
+            },
+        ),
+        (
+            "Rate Limit Per Inviter",
+            match guild.invite_poll_max_per_inviter {
+                0 => "None".to_owned(),
+                max => format!(
+                    "{} per {}",
+                    max,
+                    format_interval(&guild.invite_poll_rate_limit_window)
+                ),
+            },
+        ),
+        (
+            "Manager Role",
+            match guild.manager_role_id.as_ref() {
+                Some(role_id) => role_id.to_string(),
+                None => "None".to_owned(),
+            },
+        ),
+        ("Voter Roles", format_role_ids(&guild.voter_role_ids)),
+        ("Proposer Roles", format_role_ids(&guild.proposer_role_ids)),
+        ("Min Days To Vote", guild.min_days_to_vote.to_string()),
+        (
+            "Vote Weights",
+            if vote_weights.is_empty() {
+                "None".to_owned()
+            } else {
+                vote_weights.join("\n")
+            },
+        ),
+        (
+            "Reminders",
+            if guild.invite_poll_reminders.is_empty() {
+                "None".to_owned()
+            } else {
+                guild
+                    .invite_poll_reminders
+                    .iter()
+                    .map(format_interval)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+        ),
+        (
+            "Reminder Role",
+            match guild.reminder_role_id.as_ref() {
+                Some(role_id) => role_id.to_string(),
+                None => "None".to_owned(),
+            },
+        ),
+        (
+            "Results Channel",
+            match guild.results_channel_id.as_ref() {
+                Some(channel_id) => channel_id.to_string(),
+                None => "None".to_owned(),
+            },
+        ),
+        (
+            "Log Channel",
+            match guild.log_channel_id.as_ref() {
+                Some(channel_id) => channel_id.to_string(),
+                None => "None".to_owned(),
+            },
+        ),
+        ("Early Close", yes_no(guild.invite_poll_early_close)),
+        ("Vote Changes Allowed", yes_no(guild.allow_vote_changes)),
+    ]
+}
 
 #[derive(Debug)]
 pub struct Configure {
@@ -32,6 +382,9 @@ pub struct Configure {
     guild_id: GuildId,
     invite_channel_id: ChannelId,
     invite_poll_quorum: f32,
+    settings: GuildSettingsUpdate,
+    /// Replaces the vote weights of the guild's roles.
+    vote_weights: Option<Vec<(RoleId, i32)>>,
 }
 
 #[async_trait]
@@ -41,16 +394,48 @@ impl Action for Configure {
         let pool = POOL.get().expect("the Pool to be initialized");
         let mut transaction = pool.begin().await?;
 
-        let guild = Guild::create_or_update(
+        // check permissions
+        let member = self.interaction.member.as_deref();
+        if !is_manager(&mut *transaction, &self.guild_id, member).await? {
+            return Err(ParseActionError::InsufficientPermissions.into());
+        }
+
+        let previous = match Guild::find_by_id(&mut *transaction, &self.guild_id).await? {
+            Some(previous) => {
+                let vote_weights = find_vote_weights(&mut *transaction, &self.guild_id).await?;
+                Some(settings_fields(&previous, &vote_weights))
+            }
+            None => None,
+        };
+
+        let mut guild = Guild::create_or_update(
             &mut *transaction,
             &self.guild_id,
             &self.invite_channel_id,
             self.invite_poll_quorum,
         )
         .await?;
+        guild
+            .update_settings(&mut *transaction, &self.settings)
+            .await?;
+        if let Some(vote_weights) = self.vote_weights.as_deref() {
+            GuildVoteWeight::replace(&mut transaction, &self.guild_id, vote_weights).await?;
+        }
         trace!("updated settings: {:?}", guild);
 
-        let invite_channel = guild.invite_channel_id.to_channel(&ctx.http).await?;
+        let vote_weights = find_vote_weights(&mut *transaction, &self.guild_id).await?;
+        let fields = settings_fields(&guild, &vote_weights);
+
+        // the changed settings, or all of them when the guild is configured for the first time
+        let changes = fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (name, value))| match previous.as_ref() {
+                Some(previous) if previous[i].1 == *value => None,
+                Some(previous) => Some(format!("**{}**: {} → {}", name, previous[i].1, value)),
+                None => Some(format!("**{}**: {}", name, value)),
+            })
+            .collect::<Vec<_>>();
 
         self.interaction
             .create_response(
@@ -62,19 +447,8 @@ impl Action for Configure {
                             CreateEmbed::default()
                                 .title("Settings")
                                 .color(colors::DISCORD_BLURPLE)
-                                .field(
-                                    "Invite Channel",
-                                    match invite_channel {
-                                        Channel::Guild(ch) => ch.name,
-                                        Channel::Private(_) => "private".to_owned(),
-                                        _ => "unknown".to_owned(),
-                                    },
-                                    true,
-                                )
-                                .field(
-                                    "Required Votes",
-                                    format!("{:.0}%", guild.invite_poll_quorum * 100.0),
-                                    true,
+                                .fields(
+                                    fields.into_iter().map(|(name, value)| (name, value, true)),
                                 ),
                         ),
                 ),
@@ -83,6 +457,19 @@ impl Action for Configure {
 
         transaction.commit().await?;
 
+        if !changes.is_empty() {
+            AuditEvent::record(
+                &ctx.http,
+                pool,
+                &self.guild_id,
+                AuditEventKind::ConfigChanged,
+                Some(&self.interaction.user.id.into()),
+                None,
+                &changes.join("\n"),
+            )
+            .await;
+        }
+
         Ok(())
     }
 
@@ -106,7 +493,124 @@ impl Action for Configure {
                 .min_int_value(0)
                 .max_int_value(100)
                 .required(true),
-            )]
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                INVITE_POLL_DECISION_RULE_OPTION_NAME,
+                "veto, majority, supermajority:<percentage> or max-opposition:<count>",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                ABSTENTIONS_COUNT_TOWARD_QUORUM_OPTION_NAME,
+                "Whether abstentions count toward the minimum amount of votes of all polls",
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    KICK_POLL_QUORUM_OPTION_NAME,
+                    "The minimum amount of votes required by kick polls",
+                )
+                .min_int_value(0)
+                .max_int_value(100),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                KICK_POLL_DECISION_RULE_OPTION_NAME,
+                "The decision rule of kick polls, same format as the invite poll one",
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    VOTE_VISIBILITY_OPTION_NAME,
+                    "Who can see how each member voted on invite polls",
+                )
+                .add_string_choice("Secret", "secret")
+                .add_string_choice("Revealed After Close", "after_close")
+                .add_string_choice("Public", "public"),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                INVITE_POLL_DENY_COOLDOWN_OPTION_NAME,
+                "How long a denied or withdrawn user cannot be proposed again (e.g. `30d`), `0s` disables it",
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    INVITE_POLL_MAX_OPEN_PER_INVITER_OPTION_NAME,
+                    "The maximum number of open invite polls per inviter, `0` disables the limit",
+                )
+                .min_int_value(0)
+                .max_int_value(100),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                INVITE_POLL_RATE_LIMIT_OPTION_NAME,
+                "The maximum number of invite polls per inviter (e.g. `3/7d`), `0` disables it",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Role,
+                MANAGER_ROLE_ID_OPTION_NAME,
+                "Members with this role can manage the bot like administrators",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                VOTER_ROLE_IDS_OPTION_NAME,
+                "Roles allowed to vote on invite polls (e.g. `@Member @Elder`), or `everyone`",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                PROPOSER_ROLE_IDS_OPTION_NAME,
+                "Roles allowed to open invite polls (e.g. `@Member @Elder`), or `everyone`",
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    MIN_DAYS_TO_VOTE_OPTION_NAME,
+                    "How many days members have to be in the guild before they can vote",
+                )
+                .min_int_value(0)
+                .max_int_value(365),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                VOTE_WEIGHTS_OPTION_NAME,
+                "The vote weight of roles (e.g. `@Elder=3 @Member=2`), or `none`",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                INVITE_POLL_EARLY_CLOSE_OPTION_NAME,
+                "Whether invite polls close as soon as the remaining votes cannot change the outcome",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                INVITE_POLL_REMINDERS_OPTION_NAME,
+                "How long before the end of invite polls to remind members (e.g. `24h 1h`), or `none`",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Role,
+                REMINDER_ROLE_ID_OPTION_NAME,
+                "The role mentioned by the invite poll reminders",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Channel,
+                RESULTS_CHANNEL_ID_OPTION_NAME,
+                "The channel where the results of invite polls are announced",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Channel,
+                LOG_CHANNEL_ID_OPTION_NAME,
+                "The channel where configuration changes and poll events are logged",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                ALLOW_VOTE_CHANGES_OPTION_NAME,
+                "Whether members can change their vote on an open invite poll",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                CLEAR_OPTION_NAME,
+                "Settings to unset (e.g. `manager-role log-channel`)",
+            ))]
     }
 }
 
@@ -121,20 +625,12 @@ impl<'a> TryFrom<&'a Interaction> for Configure {
             return Err(ParseActionError::MismatchedAction);
         }
 
-        // check permissions
-        let permissions = interaction
-            .member
-            .as_ref()
-            .ok_or(ParseActionError::InsufficientPermissions)?
-            .permissions
-            .ok_or(ParseActionError::InsufficientPermissions)?;
-        if !permissions.administrator() {
-            return Err(ParseActionError::InsufficientPermissions);
-        }
-
         // options
         let mut invite_channel_id: Option<ChannelId> = None;
         let mut invite_poll_quorum: Option<f32> = None;
+        let mut settings = GuildSettingsUpdate::default();
+        let mut vote_weights: Option<Vec<(RoleId, i32)>> = None;
+        let mut cleared: Option<&str> = None;
 
         for opt in &interaction.data.options {
             match opt.name.as_str() {
@@ -147,6 +643,168 @@ impl<'a> TryFrom<&'a Interaction> for Configure {
                     let value = ((*value).clamp(0, 100) as f32) / 100.0;
                     invite_poll_quorum = Some(value);
                 }
+                name @ INVITE_POLL_DECISION_RULE_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = value.parse::<DecisionRule>().map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    settings.invite_poll_decision_rule = Some(value);
+                }
+                name @ ABSTENTIONS_COUNT_TOWARD_QUORUM_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Boolean, name)?;
+                    settings.abstentions_count_toward_quorum = Some(*value);
+                }
+                name @ KICK_POLL_QUORUM_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Integer, name)?;
+                    let value = ((*value).clamp(0, 100) as f32) / 100.0;
+                    settings.kick_poll_quorum = Some(value);
+                }
+                name @ KICK_POLL_DECISION_RULE_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = value.parse::<DecisionRule>().map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    settings.kick_poll_decision_rule = Some(value);
+                }
+                name @ VOTE_VISIBILITY_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = value.parse::<VoteVisibility>().map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    settings.vote_visibility = Some(value);
+                }
+                name @ INVITE_POLL_DENY_COOLDOWN_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = humantime::parse_duration(value)
+                        .map_err(|err| Box::new(err) as Box<_>)
+                        .and_then(PgInterval::try_from)
+                        .map_err(|err| ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: err,
+                        })?;
+                    settings.invite_poll_deny_cooldown = Some(value);
+                }
+                name @ INVITE_POLL_MAX_OPEN_PER_INVITER_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Integer, name)?;
+                    settings.invite_poll_max_open_per_inviter = Some((*value).clamp(0, 100) as i32);
+                }
+                name @ INVITE_POLL_RATE_LIMIT_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let (max, window) = parse_rate_limit(value)
+                        .map_err(|err| Box::new(err) as Box<_>)
+                        .and_then(|(max, window)| Ok((max, PgInterval::try_from(window)?)))
+                        .map_err(|err| ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: err,
+                        })?;
+                    settings.invite_poll_max_per_inviter = Some(max);
+                    settings.invite_poll_rate_limit_window = Some(window);
+                }
+                name @ MANAGER_ROLE_ID_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Role, name)?;
+                    settings.manager_role_id = Some(Some((*value).into()));
+                }
+                name @ VOTER_ROLE_IDS_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = parse_role_ids(value).map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    settings.voter_role_ids = Some(value);
+                }
+                name @ PROPOSER_ROLE_IDS_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = parse_role_ids(value).map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    settings.proposer_role_ids = Some(value);
+                }
+                name @ MIN_DAYS_TO_VOTE_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Integer, name)?;
+                    settings.min_days_to_vote = Some((*value).clamp(0, 365) as i32);
+                }
+                name @ INVITE_POLL_REMINDERS_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = parse_reminders(value)
+                        .map_err(|err| Box::new(err) as Box<_>)
+                        .and_then(|durations| {
+                            durations
+                                .into_iter()
+                                .map(PgInterval::try_from)
+                                .collect::<Result<Vec<_>, _>>()
+                        })
+                        .map_err(|err| ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: err,
+                        })?;
+                    settings.invite_poll_reminders = Some(value);
+                }
+                name @ RESULTS_CHANNEL_ID_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Channel, name)?;
+                    settings.results_channel_id = Some(Some((*value).into()));
+                }
+                name @ LOG_CHANNEL_ID_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Channel, name)?;
+                    settings.log_channel_id = Some(Some((*value).into()));
+                }
+                name @ REMINDER_ROLE_ID_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Role, name)?;
+                    settings.reminder_role_id = Some(Some((*value).into()));
+                }
+                name @ INVITE_POLL_EARLY_CLOSE_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Boolean, name)?;
+                    settings.invite_poll_early_close = Some(*value);
+                }
+                name @ ALLOW_VOTE_CHANGES_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Boolean, name)?;
+                    settings.allow_vote_changes = Some(*value);
+                }
+                name @ VOTE_WEIGHTS_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = parse_vote_weights(value).map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    vote_weights = Some(value);
+                }
+                name @ CLEAR_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    cleared = Some(value.as_str());
+                }
                 other => {
                     return Err(ParseActionError::UnknownOption {
                         action: ACTION_ID,
@@ -156,6 +814,22 @@ impl<'a> TryFrom<&'a Interaction> for Configure {
             }
         }
 
+        // cleared after the other options to detect the settings which are also set
+        if let Some(value) = cleared {
+            parse_cleared_settings(value)
+                .and_then(|settings_to_clear| {
+                    settings_to_clear
+                        .iter()
+                        .try_for_each(|setting| setting.clear(&mut settings))
+                })
+                .map_err(|err| ParseActionError::InvalidOptionValue {
+                    action: ACTION_ID,
+                    option: CLEAR_OPTION_NAME.into(),
+                    value: value.to_string(),
+                    source: Box::new(err),
+                })?;
+        }
+
         let invite_channel_id = invite_channel_id.ok_or(ParseActionError::MissingOption {
             action: ACTION_ID,
             option: INVITE_CHANNEL_ID_OPTION_NAME.into(),
@@ -175,6 +849,114 @@ impl<'a> TryFrom<&'a Interaction> for Configure {
             guild_id,
             invite_channel_id,
             invite_poll_quorum,
+            settings,
+            vote_weights,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role_ids(role_ids: &[RoleId]) -> Vec<u64> {
+        role_ids.iter().map(|role_id| role_id.get()).collect()
+    }
+
+    #[test]
+    fn test_parse_role_ids() {
+        assert_eq!(
+            role_ids(&parse_role_ids("<@&1> 2,<@&3>").unwrap()),
+            vec![1, 2, 3]
+        );
+        assert!(parse_role_ids(" Everyone ").unwrap().is_empty());
+        assert!(parse_role_ids("@Member").is_err());
+        assert!(parse_role_ids("<@&0>").is_err());
+    }
+
+    #[test]
+    fn test_parse_vote_weights() {
+        let weights = parse_vote_weights("<@&1>=3, 2=1").unwrap();
+        assert_eq!(
+            weights
+                .iter()
+                .map(|(role_id, weight)| (role_id.get(), *weight))
+                .collect::<Vec<_>>(),
+            vec![(1, 3), (2, 1)]
+        );
+        assert!(parse_vote_weights("NONE").unwrap().is_empty());
+        assert!(matches!(
+            parse_vote_weights("<@&1>"),
+            Err(ParseVoteWeightsError::MissingSeparator(_))
+        ));
+        assert!(matches!(
+            parse_vote_weights("<@&0>=2"),
+            Err(ParseVoteWeightsError::InvalidRole(_, _))
+        ));
+        assert!(matches!(
+            parse_vote_weights("<@&1>=many"),
+            Err(ParseVoteWeightsError::InvalidWeight(_, _))
+        ));
+        assert!(matches!(
+            parse_vote_weights("<@&1>=0"),
+            Err(ParseVoteWeightsError::WeightOutOfRange(0))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(
+            parse_rate_limit(" 3 / 7d ").unwrap(),
+            (3, Duration::from_secs(7 * 24 * 60 * 60))
+        );
+        assert_eq!(parse_rate_limit("0").unwrap(), (0, Duration::ZERO));
+        assert!(matches!(
+            parse_rate_limit("3"),
+            Err(ParseRateLimitError::MissingSeparator)
+        ));
+        assert!(matches!(
+            parse_rate_limit("-1/7d"),
+            Err(ParseRateLimitError::InvalidCount(_))
+        ));
+        assert!(matches!(
+            parse_rate_limit("3/soon"),
+            Err(ParseRateLimitError::InvalidDuration(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_reminders() {
+        assert_eq!(
+            parse_reminders("24h, 1h 30m").unwrap(),
+            vec![
+                Duration::from_secs(24 * 60 * 60),
+                Duration::from_secs(60 * 60),
+                Duration::from_secs(30 * 60)
+            ]
+        );
+        assert!(parse_reminders("none").unwrap().is_empty());
+        assert!(parse_reminders("tomorrow").is_err());
+    }
+
+    #[test]
+    fn test_parse_cleared_settings() {
+        assert_eq!(
+            parse_cleared_settings("manager-role, Log-Channel").unwrap(),
+            vec![ClearableSetting::ManagerRole, ClearableSetting::LogChannel]
+        );
+        assert!(parse_cleared_settings("").unwrap().is_empty());
+        assert!(parse_cleared_settings("invite-channel").is_err());
+    }
+
+    #[test]
+    fn test_clearable_setting_clear() {
+        let mut settings = GuildSettingsUpdate::default();
+        ClearableSetting::ResultsChannel
+            .clear(&mut settings)
+            .unwrap();
+        assert!(matches!(settings.results_channel_id, Some(None)));
+
+        settings.log_channel_id = Some(Some("1".parse().unwrap()));
+        assert!(ClearableSetting::LogChannel.clear(&mut settings).is_err());
+    }
+}
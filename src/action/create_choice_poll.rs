@@ -0,0 +1,315 @@
+use std::time::Duration;
+
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    async_trait,
+    builder::{
+        CreateCommand, CreateCommandOption, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateMessage,
+    },
+    model::prelude::Interaction,
+    prelude::Context,
+};
+
+use crate::{
+    entities::{
+        AuditEvent, AuditEventKind, ChoicePoll, ChoicePollMode, ChoicePollOption,
+        ChoicePollOptionWithVoteCount, ChoicePollWithVoteCount, PollRef,
+    },
+    error::Error,
+    resolve_option,
+    util::serenity::{GuildId, UserId},
+    POOL,
+};
+
+use super::{Action, ParseActionError};
+
+const ACTION_ID: &'static str = "choice-poll";
+const QUESTION_OPTION_NAME: &'static str = "question";
+const CHOICES_OPTION_NAME: &'static str = "choices";
+const DESCRIPTION_OPTION_NAME: &'static str = "description";
+const DURATION_OPTION_NAME: &'static str = "duration";
+const RANKED_OPTION_NAME: &'static str = "ranked";
+
+const CHOICES_SEPARATOR: char = ';';
+const MIN_CHOICES: usize = 2;
+const MAX_CHOICES: usize = 25;
+const MAX_CHOICE_LENGTH: usize = 80;
+
+#[derive(Debug, thiserror::Error)]
+enum ParseChoicesError {
+    #[error(
+        "expected between {} and {} choices, got {0}",
+        MIN_CHOICES,
+        MAX_CHOICES
+    )]
+    InvalidCount(usize),
+
+    #[error("choice `{0}` is longer than {} characters", MAX_CHOICE_LENGTH)]
+    TooLong(String),
+
+    #[error("choice `{0}` is repeated")]
+    Repeated(String),
+}
+
+fn parse_choices(value: &str) -> Result<Vec<String>, ParseChoicesError> {
+    let mut res: Vec<String> = Vec::new();
+
+    for choice in value.split(CHOICES_SEPARATOR).map(str::trim) {
+        if choice.is_empty() {
+            continue;
+        }
+        if choice.chars().count() > MAX_CHOICE_LENGTH {
+            return Err(ParseChoicesError::TooLong(choice.to_owned()));
+        }
+        if res.iter().any(|c| c.eq_ignore_ascii_case(choice)) {
+            return Err(ParseChoicesError::Repeated(choice.to_owned()));
+        }
+
+        res.push(choice.to_owned());
+    }
+
+    if !(MIN_CHOICES..=MAX_CHOICES).contains(&res.len()) {
+        return Err(ParseChoicesError::InvalidCount(res.len()));
+    }
+
+    Ok(res)
+}
+
+#[derive(Debug)]
+pub struct CreateChoicePoll {
+    interaction: CommandInteraction,
+    guild_id: GuildId,
+    author: UserId,
+    question: String,
+    choices: Vec<String>,
+    description: Option<String>,
+    mode: ChoicePollMode,
+    duration: Duration,
+}
+
+#[async_trait]
+impl Action for CreateChoicePoll {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+        let mut transaction = pool.begin().await?;
+
+        // create poll
+        let choice_poll = ChoicePoll::create(
+            &mut *transaction,
+            &self.guild_id,
+            &self.author,
+            &self.question,
+            self.description.as_deref(),
+            self.mode,
+            &self.duration,
+        )
+        .await?;
+
+        let mut options = Vec::with_capacity(self.choices.len());
+        for (position, label) in self.choices.iter().enumerate() {
+            let option = ChoicePollOption::create(
+                &mut *transaction,
+                &choice_poll.id,
+                position as i16,
+                label,
+            )
+            .await?;
+
+            options.push(ChoicePollOptionWithVoteCount {
+                option,
+                vote_count: 0,
+            });
+        }
+
+        // render poll
+        let mut choice_poll = ChoicePollWithVoteCount {
+            choice_poll,
+            options,
+            ballots: Vec::new(),
+        };
+
+        let renderer = choice_poll.create_renderer(ctx.clone()).await?;
+        let msg = self
+            .interaction
+            .channel_id
+            .send_message(
+                &ctx.http,
+                renderer.render_create_message(CreateMessage::default()),
+            )
+            .await?;
+
+        choice_poll
+            .choice_poll
+            .update_message(&mut *transaction, &msg)
+            .await?;
+
+        self.interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::default()
+                        .ephemeral(true)
+                        .content(format!(
+                            "https://discord.com/channels/{}/{}/{}",
+                            self.guild_id.get(),
+                            msg.channel_id,
+                            msg.id
+                        )),
+                ),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        AuditEvent::record(
+            &ctx.http,
+            pool,
+            &self.guild_id,
+            AuditEventKind::PollCreated,
+            Some(&self.author),
+            Some(&PollRef::Choice(choice_poll.choice_poll.id.clone())),
+            &format!("choice poll \"{}\"", self.question),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    fn register() -> Vec<CreateCommand> {
+        vec![CreateCommand::new(ACTION_ID)
+            .description("Asks the community to pick one of multiple choices")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    QUESTION_OPTION_NAME,
+                    "The question to vote on",
+                )
+                .max_length(256)
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    CHOICES_OPTION_NAME,
+                    "Between 2 and 25 choices separated by `;`",
+                )
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    DESCRIPTION_OPTION_NAME,
+                    "Additional details about the question",
+                )
+                .max_length(1024),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                DURATION_OPTION_NAME,
+                "Duration of the poll",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                RANKED_OPTION_NAME,
+                "Let voters rank the choices and decide the winner by instant-runoff",
+            ))]
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for CreateChoicePoll {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_command()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if interaction.data.name != ACTION_ID {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        // options
+        let mut question: Option<String> = None;
+        let mut choices: Option<Vec<String>> = None;
+        let mut description: Option<String> = None;
+        let mut duration: Option<Duration> = None;
+        let mut ranked: Option<bool> = None;
+
+        for opt in &interaction.data.options {
+            match opt.name.as_str() {
+                name @ QUESTION_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    question = Some(value.to_owned());
+                }
+                name @ CHOICES_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = parse_choices(value).map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    choices = Some(value);
+                }
+                name @ DESCRIPTION_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    description = Some(value.to_owned());
+                }
+                name @ DURATION_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = humantime::parse_duration(value).map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    duration = Some(value);
+                }
+                name @ RANKED_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Boolean, name)?;
+                    ranked = Some(*value);
+                }
+                other => {
+                    return Err(ParseActionError::UnknownOption {
+                        action: ACTION_ID,
+                        option: other.to_owned(),
+                    });
+                }
+            }
+        }
+
+        let question = question.ok_or(ParseActionError::MissingOption {
+            action: ACTION_ID,
+            option: QUESTION_OPTION_NAME.into(),
+        })?;
+        let choices = choices.ok_or(ParseActionError::MissingOption {
+            action: ACTION_ID,
+            option: CHOICES_OPTION_NAME.into(),
+        })?;
+        let duration = duration.unwrap_or(Duration::from_secs(3 * 24 * 60 * 60)); // 3 days
+        let mode = match ranked {
+            Some(true) => ChoicePollMode::Ranked,
+            Some(false) | None => ChoicePollMode::Single,
+        };
+
+        let guild_id = interaction
+            .guild_id
+            .ok_or(ParseActionError::NotInAGuild { action: ACTION_ID })
+            .map(Into::into)?;
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            guild_id,
+            author: interaction.user.id.into(),
+            question,
+            choices,
+            description,
+            mode,
+            duration,
+        })
+    }
+}
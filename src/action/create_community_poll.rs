@@ -0,0 +1,256 @@
+use std::time::Duration;
+
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    async_trait,
+    builder::{
+        CreateCommand, CreateCommandOption, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateMessage,
+    },
+    model::prelude::Interaction,
+    prelude::Context,
+};
+
+use crate::{
+    entities::{
+        AuditEvent, AuditEventKind, CommunityPoll, CommunityPollWithVoteCount, DecisionRule,
+        NewCommunityPoll, PollRef,
+    },
+    error::Error,
+    resolve_option,
+    util::serenity::{GuildId, UserId},
+    POOL,
+};
+
+use super::{Action, ParseActionError};
+
+const ACTION_ID: &'static str = "poll";
+const QUESTION_OPTION_NAME: &'static str = "question";
+const DESCRIPTION_OPTION_NAME: &'static str = "description";
+const DURATION_OPTION_NAME: &'static str = "duration";
+const QUORUM_OPTION_NAME: &'static str = "quorum";
+const DECISION_RULE_OPTION_NAME: &'static str = "decision-rule";
+
+#[derive(Debug)]
+pub struct CreateCommunityPoll {
+    interaction: CommandInteraction,
+    guild_id: GuildId,
+    author: UserId,
+    question: String,
+    description: Option<String>,
+    duration: Duration,
+    quorum: f32,
+    decision_rule: DecisionRule,
+}
+
+#[async_trait]
+impl Action for CreateCommunityPoll {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+        let mut transaction = pool.begin().await?;
+
+        // create poll
+        let community_poll = CommunityPoll::create(
+            &mut *transaction,
+            &NewCommunityPoll {
+                guild_id: &self.guild_id,
+                author: &self.author,
+                question: &self.question,
+                description: self.description.as_deref(),
+                quorum: self.quorum,
+                decision_rule: self.decision_rule,
+                duration: &self.duration,
+            },
+        )
+        .await?;
+
+        // render poll
+        let mut community_poll = CommunityPollWithVoteCount {
+            community_poll,
+            yes_count: 0,
+            no_count: 0,
+            abstain_count: 0,
+        };
+
+        let renderer = community_poll.create_renderer(ctx.clone()).await?;
+        let msg = self
+            .interaction
+            .channel_id
+            .send_message(
+                &ctx.http,
+                renderer.render_create_message(CreateMessage::default()),
+            )
+            .await?;
+
+        community_poll
+            .community_poll
+            .update_message(&mut *transaction, &msg)
+            .await?;
+
+        self.interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::default()
+                        .ephemeral(true)
+                        .content(format!(
+                            "https://discord.com/channels/{}/{}/{}",
+                            self.guild_id.get(),
+                            msg.channel_id,
+                            msg.id
+                        )),
+                ),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        AuditEvent::record(
+            &ctx.http,
+            pool,
+            &self.guild_id,
+            AuditEventKind::PollCreated,
+            Some(&self.author),
+            Some(&PollRef::Community(
+                community_poll.community_poll.id.clone(),
+            )),
+            &format!("community poll \"{}\"", self.question),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    fn register() -> Vec<CreateCommand> {
+        vec![CreateCommand::new(ACTION_ID)
+            .description("Asks the community a yes/no question")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    QUESTION_OPTION_NAME,
+                    "The question to vote on",
+                )
+                .max_length(256)
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    DESCRIPTION_OPTION_NAME,
+                    "Additional details about the question",
+                )
+                .max_length(4096),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                DURATION_OPTION_NAME,
+                "Duration of the poll",
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    QUORUM_OPTION_NAME,
+                    "The minimum amount of votes required",
+                )
+                .min_int_value(0)
+                .max_int_value(100),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                DECISION_RULE_OPTION_NAME,
+                "veto, majority, supermajority:<percentage> or max-opposition:<count>",
+            ))]
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for CreateCommunityPoll {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_command()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if interaction.data.name != ACTION_ID {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        // options
+        let mut question: Option<String> = None;
+        let mut description: Option<String> = None;
+        let mut duration: Option<Duration> = None;
+        let mut quorum: Option<f32> = None;
+        let mut decision_rule: Option<DecisionRule> = None;
+
+        for opt in &interaction.data.options {
+            match opt.name.as_str() {
+                name @ QUESTION_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    question = Some(value.to_owned());
+                }
+                name @ DESCRIPTION_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    description = Some(value.to_owned());
+                }
+                name @ DURATION_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = humantime::parse_duration(value).map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    duration = Some(value);
+                }
+                name @ QUORUM_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, Integer, name)?;
+                    let value = ((*value).clamp(0, 100) as f32) / 100.0;
+                    quorum = Some(value);
+                }
+                name @ DECISION_RULE_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = value.parse::<DecisionRule>().map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    decision_rule = Some(value);
+                }
+                other => {
+                    return Err(ParseActionError::UnknownOption {
+                        action: ACTION_ID,
+                        option: other.to_owned(),
+                    });
+                }
+            }
+        }
+
+        let question = question.ok_or(ParseActionError::MissingOption {
+            action: ACTION_ID,
+            option: QUESTION_OPTION_NAME.into(),
+        })?;
+        let duration = duration.unwrap_or(Duration::from_secs(3 * 24 * 60 * 60)); // 3 days
+        let quorum = quorum.unwrap_or(0.0);
+        let decision_rule = decision_rule.unwrap_or(DecisionRule::Majority);
+
+        let guild_id = interaction
+            .guild_id
+            .ok_or(ParseActionError::NotInAGuild { action: ACTION_ID })
+            .map(Into::into)?;
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            guild_id,
+            author: interaction.user.id.into(),
+            question,
+            description,
+            duration,
+            quorum,
+            decision_rule,
+        })
+    }
+}
@@ -12,18 +12,25 @@ use serenity::{
 };
 
 use crate::{
-    entities::{InvitePoll, InvitePollWithVoteCount},
+    entities::{
+        AuditEvent, AuditEventKind, Guild, InvitePoll, InvitePollWithVoteCount, PollRef,
+        VoteVisibility, INVITE_POLL_OPEN_INVITEE_KEY,
+    },
     error::Error,
     resolve_option,
-    util::serenity::{GuildExt, GuildId, UserId},
+    util::{
+        serenity::{GuildExt, GuildId, UserId},
+        DiscordTimestamp, DiscordTimestampStyle,
+    },
     POOL,
 };
 
-use super::{Action, ParseActionError};
+use super::{util::duplicate_invite_poll_error, Action, ParseActionError};
 
 const ACTION_ID: &'static str = "invite";
 const USER_ID_OPTION_NAME: &'static str = "user-id";
 const DURATION_OPTION_NAME: &'static str = "duration";
+const VOTE_VISIBILITY_OPTION_NAME: &'static str = "vote-visibility";
 
 #[derive(Debug)]
 pub struct CreateInvitePoll {
@@ -31,6 +38,8 @@ pub struct CreateInvitePoll {
     guild_id: GuildId,
     inviter: UserId,
     invitee: UserId,
+    /// Overrides the guild's default vote visibility.
+    vote_visibility: Option<VoteVisibility>,
     duration: Duration,
 }
 
@@ -41,26 +50,77 @@ impl Action for CreateInvitePoll {
         let mut transaction = pool.begin().await?;
 
         // preliminary checks
+        let settings = Guild::find_by_id(&mut *transaction, &self.guild_id)
+            .await?
+            .ok_or_else(|| Error::GuildNotFound(self.guild_id.clone()))?;
+        let member = self.interaction.member.as_deref();
+        if !member.is_some_and(|member| settings.is_eligible_proposer(member)) {
+            return Err(Error::MissingProposerRole);
+        }
+
         let guild = self.guild_id.to_partial_guild(&ctx.http).await?;
         if guild.is_member(&ctx.http, &self.invitee).await? {
             return Err(Error::CannotInviteMember(self.invitee.clone()));
         }
+        if let Some(existing) =
+            InvitePoll::find_open_by_invitee(&mut *transaction, &self.guild_id, &self.invitee)
+                .await?
+        {
+            return Err(duplicate_invite_poll_error(&existing));
+        }
+        if let Some(cooldown_end) =
+            InvitePoll::find_deny_cooldown_end(&mut *transaction, &self.guild_id, &self.invitee)
+                .await?
+        {
+            return Err(Error::InviteeOnCooldown(
+                self.invitee.clone(),
+                DiscordTimestamp::new(cooldown_end, DiscordTimestampStyle::FullShort),
+            ));
+        }
+        if let Some(reset) =
+            InvitePoll::find_rate_limit_reset(&mut *transaction, &self.guild_id, &self.inviter)
+                .await?
+        {
+            return Err(Error::InvitePollRateLimited(DiscordTimestamp::new(
+                reset,
+                DiscordTimestampStyle::Relative,
+            )));
+        }
 
         // create poll
-        let invite_poll = InvitePoll::create(
+        let invite_poll = match InvitePoll::create(
             &mut *transaction,
             &self.guild_id,
             &self.inviter,
             &self.invitee,
+            self.vote_visibility,
             &self.duration,
         )
-        .await?;
+        .await
+        {
+            Ok(invite_poll) => invite_poll,
+            // another poll for the same invitee was created concurrently
+            Err(err) if err.is_constraint_violation(INVITE_POLL_OPEN_INVITEE_KEY) => {
+                return match InvitePoll::find_open_by_invitee(pool, &self.guild_id, &self.invitee)
+                    .await?
+                {
+                    Some(existing) => Err(duplicate_invite_poll_error(&existing)),
+                    None => Err(err),
+                };
+            }
+            Err(err) => return Err(err),
+        };
 
         // render poll
         let mut invite_poll = InvitePollWithVoteCount {
             invite_poll,
             yes_count: 0,
             no_count: 0,
+            abstain_count: 0,
+            delegated_count: 0,
+            voter_count: 0,
+            votes: Vec::new(),
+            reasons: Vec::new(),
         };
 
         let renderer = invite_poll.create_renderer(ctx.clone()).await?;
@@ -96,6 +156,17 @@ impl Action for CreateInvitePoll {
 
         transaction.commit().await?;
 
+        AuditEvent::record(
+            &ctx.http,
+            pool,
+            &self.guild_id,
+            AuditEventKind::PollCreated,
+            Some(&self.inviter),
+            Some(&PollRef::Invite(invite_poll.invite_poll.id.clone())),
+            &format!("invite poll for {}", self.invitee),
+        )
+        .await;
+
         Ok(())
     }
 
@@ -114,7 +185,17 @@ impl Action for CreateInvitePoll {
                 CommandOptionType::String,
                 DURATION_OPTION_NAME,
                 "Duration of the poll",
-            ))]
+            ))
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    VOTE_VISIBILITY_OPTION_NAME,
+                    "Who can see how each member voted, defaults to the guild setting",
+                )
+                .add_string_choice("Secret", "secret")
+                .add_string_choice("Revealed After Close", "after_close")
+                .add_string_choice("Public", "public"),
+            )]
     }
 }
 
@@ -132,6 +213,7 @@ impl<'a> TryFrom<&'a Interaction> for CreateInvitePoll {
         // options
         let mut user_id: Option<UserId> = None;
         let mut duration: Option<Duration> = None;
+        let mut vote_visibility: Option<VoteVisibility> = None;
 
         for opt in &interaction.data.options {
             match opt.name.as_str() {
@@ -159,6 +241,18 @@ impl<'a> TryFrom<&'a Interaction> for CreateInvitePoll {
                     })?;
                     duration = Some(value);
                 }
+                name @ VOTE_VISIBILITY_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = value.parse::<VoteVisibility>().map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    vote_visibility = Some(value);
+                }
                 other => {
                     return Err(ParseActionError::UnknownOption {
                         action: ACTION_ID,
@@ -184,6 +278,7 @@ impl<'a> TryFrom<&'a Interaction> for CreateInvitePoll {
             guild_id,
             inviter: interaction.user.id.into(),
             invitee: user_id,
+            vote_visibility,
             duration,
         })
     }
@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    async_trait,
+    builder::{
+        CreateCommand, CreateCommandOption, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateMessage,
+    },
+    model::prelude::Interaction,
+    prelude::Context,
+};
+
+use crate::{
+    entities::{AuditEvent, AuditEventKind, KickPoll, KickPollWithVoteCount, PollRef},
+    error::Error,
+    resolve_option,
+    util::serenity::{GuildExt, GuildId, UserId},
+    POOL,
+};
+
+use super::{Action, ParseActionError};
+
+const ACTION_ID: &'static str = "kick-poll";
+const USER_OPTION_NAME: &'static str = "user";
+const DURATION_OPTION_NAME: &'static str = "duration";
+
+#[derive(Debug)]
+pub struct CreateKickPoll {
+    interaction: CommandInteraction,
+    guild_id: GuildId,
+    initiator: UserId,
+    target: UserId,
+    duration: Duration,
+}
+
+#[async_trait]
+impl Action for CreateKickPoll {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+        let mut transaction = pool.begin().await?;
+
+        // preliminary checks
+        let guild = self.guild_id.to_partial_guild(&ctx.http).await?;
+        if guild.owner_id == *self.target {
+            return Err(Error::CannotKickOwner(self.target.clone()));
+        }
+        if !guild.is_member(&ctx.http, &self.target).await? {
+            return Err(Error::CannotKickNonMember(self.target.clone()));
+        }
+
+        // create poll
+        let kick_poll = KickPoll::create(
+            &mut *transaction,
+            &self.guild_id,
+            &self.initiator,
+            &self.target,
+            &self.duration,
+        )
+        .await?;
+
+        // render poll
+        let mut kick_poll = KickPollWithVoteCount {
+            kick_poll,
+            yes_count: 0,
+            no_count: 0,
+            abstain_count: 0,
+        };
+
+        let renderer = kick_poll.create_renderer(ctx.clone()).await?;
+        let msg = self
+            .interaction
+            .channel_id
+            .send_message(
+                &ctx.http,
+                renderer.render_create_message(CreateMessage::default()),
+            )
+            .await?;
+
+        kick_poll
+            .kick_poll
+            .update_message(&mut *transaction, &msg)
+            .await?;
+
+        self.interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::default()
+                        .ephemeral(true)
+                        .content(format!(
+                            "https://discord.com/channels/{}/{}/{}",
+                            self.guild_id.get(),
+                            msg.channel_id,
+                            msg.id
+                        )),
+                ),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        AuditEvent::record(
+            &ctx.http,
+            pool,
+            &self.guild_id,
+            AuditEventKind::PollCreated,
+            Some(&self.initiator),
+            Some(&PollRef::Kick(kick_poll.kick_poll.id.clone())),
+            &format!("kick poll for {}", self.target),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    fn register() -> Vec<CreateCommand> {
+        vec![CreateCommand::new(ACTION_ID)
+            .description("Creates a petition to remove a member")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    USER_OPTION_NAME,
+                    "The member to remove",
+                )
+                .required(true),
+            )
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                DURATION_OPTION_NAME,
+                "Duration of the poll",
+            ))]
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for CreateKickPoll {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_command()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if interaction.data.name != ACTION_ID {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        // options
+        let mut user_id: Option<UserId> = None;
+        let mut duration: Option<Duration> = None;
+
+        for opt in &interaction.data.options {
+            match opt.name.as_str() {
+                name @ USER_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, User, name)?;
+                    user_id = Some((*value).into());
+                }
+                name @ DURATION_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                    let value = humantime::parse_duration(value).map_err(|err| {
+                        ParseActionError::InvalidOptionValue {
+                            action: ACTION_ID,
+                            option: name.into(),
+                            value: value.to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+                    duration = Some(value);
+                }
+                other => {
+                    return Err(ParseActionError::UnknownOption {
+                        action: ACTION_ID,
+                        option: other.to_owned(),
+                    });
+                }
+            }
+        }
+
+        let user_id = user_id.ok_or(ParseActionError::MissingOption {
+            action: ACTION_ID,
+            option: USER_OPTION_NAME.into(),
+        })?;
+        let duration = duration.unwrap_or(Duration::from_secs(3 * 24 * 60 * 60)); // 3 days
+
+        let guild_id = interaction
+            .guild_id
+            .ok_or(ParseActionError::NotInAGuild { action: ACTION_ID })
+            .map(Into::into)?;
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            guild_id,
+            initiator: interaction.user.id.into(),
+            target: user_id,
+            duration,
+        })
+    }
+}
@@ -0,0 +1,160 @@
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    async_trait,
+    builder::{
+        CreateCommand, CreateCommandOption, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    model::prelude::Interaction,
+    prelude::Context,
+};
+
+use crate::{
+    entities::{Delegation, Guild},
+    error::Error,
+    resolve_option,
+    util::{
+        serenity::{GuildExt, GuildId, UserId},
+        DiscordTimestamp, DiscordTimestampStyle,
+    },
+    POOL,
+};
+
+use super::{Action, ParseActionError};
+
+const ACTION_ID: &'static str = "delegate";
+const MEMBER_OPTION_NAME: &'static str = "member";
+
+#[derive(Debug)]
+pub struct Delegate {
+    interaction: CommandInteraction,
+    guild_id: GuildId,
+    delegator: UserId,
+    /// The member to delegate to, `None` revokes the current delegation.
+    delegate: Option<UserId>,
+}
+
+#[async_trait]
+impl Action for Delegate {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        let content = match &self.delegate {
+            Some(delegate) => {
+                if delegate.get() == self.delegator.get() {
+                    return Err(Error::CannotDelegateToSelf);
+                }
+
+                let settings = Guild::find_by_id(pool, &self.guild_id)
+                    .await?
+                    .ok_or_else(|| Error::GuildNotFound(self.guild_id.clone()))?;
+
+                // only eligible voters have a vote to delegate
+                let member = self
+                    .interaction
+                    .member
+                    .as_ref()
+                    .ok_or(Error::MissingVoterRole)?;
+                if !settings.has_voter_role(member) {
+                    return Err(Error::MissingVoterRole);
+                }
+                if let Some(allowed_at) = settings.voting_allowed_at(member) {
+                    return Err(Error::InsufficientTenure(DiscordTimestamp::new(
+                        allowed_at,
+                        DiscordTimestampStyle::Relative,
+                    )));
+                }
+
+                let guild = self.guild_id.to_partial_guild(&ctx.http).await?;
+                match guild.find_member(&ctx.http, delegate).await? {
+                    Some(member) if member.user.bot => {
+                        return Err(Error::CannotDelegateToBot(delegate.clone()));
+                    }
+                    Some(_) => {}
+                    None => return Err(Error::CannotDelegateToNonMember(delegate.clone())),
+                }
+
+                Delegation::create_or_update(pool, &self.guild_id, &self.delegator, delegate)
+                    .await?;
+
+                format!(
+                    "Your vote will be delegated to {} in the polls you do not vote in.",
+                    delegate
+                )
+            }
+            None => match Delegation::delete(pool, &self.guild_id, &self.delegator).await? {
+                Some(delegation) => format!(
+                    "Your vote is no longer delegated to {}.",
+                    delegation.delegate
+                ),
+                None => "Your vote is not delegated to anyone.".to_string(),
+            },
+        };
+
+        self.interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::default()
+                        .ephemeral(true)
+                        .content(content),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn register() -> Vec<CreateCommand> {
+        vec![CreateCommand::new(ACTION_ID)
+            .description("Delegates your vote in the polls you do not vote in to another member")
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::User,
+                MEMBER_OPTION_NAME,
+                "The member to delegate to, leave empty to revoke the delegation",
+            ))]
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for Delegate {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_command()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if interaction.data.name != ACTION_ID {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        // options
+        let mut delegate: Option<UserId> = None;
+
+        for opt in &interaction.data.options {
+            match opt.name.as_str() {
+                name @ MEMBER_OPTION_NAME => {
+                    let value = resolve_option!(ACTION_ID, &opt.value, User, name)?;
+                    delegate = Some((*value).into());
+                }
+                other => {
+                    return Err(ParseActionError::UnknownOption {
+                        action: ACTION_ID,
+                        option: other.to_owned(),
+                    });
+                }
+            }
+        }
+
+        let guild_id = interaction
+            .guild_id
+            .ok_or(ParseActionError::NotInAGuild { action: ACTION_ID })
+            .map(Into::into)?;
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            guild_id,
+            delegator: interaction.user.id.into(),
+            delegate,
+        })
+    }
+}
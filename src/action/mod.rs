@@ -1,14 +1,47 @@
 use crate::create_actions;
 
 pub use self::{
-    action::*, configure::*, create_invite_poll::*, error::*, submit_invite_poll_vote::*,
+    action::*, cancel_invite_poll::*, configure::*, create_choice_poll::*,
+    create_community_poll::*, create_invite_poll::*, create_kick_poll::*, delegate::*, error::*,
+    notifications::*, poll_admin::*, rank_choice_poll::*, submit_choice_poll_vote::*,
+    submit_community_poll_vote::*, submit_invite_poll_vote::*, submit_invite_poll_vote_reason::*,
+    submit_kick_poll_vote::*,
 };
 
 mod action;
+mod cancel_invite_poll;
 mod configure;
+mod create_choice_poll;
+mod create_community_poll;
 mod create_invite_poll;
+mod create_kick_poll;
+mod delegate;
 mod error;
+mod notifications;
+mod poll_admin;
+mod rank_choice_poll;
+mod submit_choice_poll_vote;
+mod submit_community_poll_vote;
 mod submit_invite_poll_vote;
+mod submit_invite_poll_vote_reason;
+mod submit_kick_poll_vote;
 mod util;
 
-create_actions!(Actions, Configure, CreateInvitePoll, SubmitInvitePollVote);
+create_actions!(
+    Actions,
+    CancelInvitePoll,
+    Configure,
+    CreateChoicePoll,
+    CreateCommunityPoll,
+    CreateInvitePoll,
+    CreateKickPoll,
+    Delegate,
+    Notifications,
+    PollAdmin,
+    RankChoicePoll,
+    SubmitChoicePollVote,
+    SubmitCommunityPollVote,
+    SubmitInvitePollVote,
+    SubmitInvitePollVoteReason,
+    SubmitKickPollVote
+);
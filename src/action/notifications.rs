@@ -0,0 +1,130 @@
+use serenity::{
+    all::{CommandInteraction, CommandOptionType},
+    async_trait,
+    builder::{
+        CreateCommand, CreateCommandOption, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    },
+    model::prelude::Interaction,
+    prelude::Context,
+};
+
+use crate::{
+    entities::{Guild, NotificationPreference},
+    error::Error,
+    util::serenity::{GuildId, UserId},
+    POOL,
+};
+
+use super::{Action, ParseActionError};
+
+const ACTION_ID: &'static str = "notifications";
+const ON_SUBCOMMAND_NAME: &'static str = "on";
+const OFF_SUBCOMMAND_NAME: &'static str = "off";
+
+#[derive(Debug)]
+pub struct Notifications {
+    interaction: CommandInteraction,
+    guild_id: GuildId,
+    user_id: UserId,
+    /// Whether the member wants to be nudged about the invite polls they have not voted on.
+    vote_nudges: bool,
+}
+
+#[async_trait]
+impl Action for Notifications {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        Guild::find_by_id(pool, &self.guild_id)
+            .await?
+            .ok_or_else(|| Error::GuildNotFound(self.guild_id.clone()))?;
+
+        NotificationPreference::create_or_update(
+            pool,
+            &self.guild_id,
+            &self.user_id,
+            self.vote_nudges,
+        )
+        .await?;
+
+        self.interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::default()
+                        .ephemeral(true)
+                        .content(if self.vote_nudges {
+                            "You will receive a direct message when an invite poll you have not voted on is about to end."
+                        } else {
+                            "You will no longer receive direct messages about the invite polls you have not voted on."
+                        }),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn register() -> Vec<CreateCommand> {
+        vec![CreateCommand::new(ACTION_ID)
+            .description("Manages your notifications")
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                ON_SUBCOMMAND_NAME,
+                "Get a direct message when an invite poll you have not voted on is about to end",
+            ))
+            .add_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                OFF_SUBCOMMAND_NAME,
+                "Stop getting direct messages about the invite polls you have not voted on",
+            ))]
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for Notifications {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_command()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if interaction.data.name != ACTION_ID {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        // subcommand
+        let subcommand =
+            interaction
+                .data
+                .options
+                .first()
+                .ok_or(ParseActionError::MissingOption {
+                    action: ACTION_ID,
+                    option: "subcommand".into(),
+                })?;
+
+        let vote_nudges = match subcommand.name.as_str() {
+            ON_SUBCOMMAND_NAME => true,
+            OFF_SUBCOMMAND_NAME => false,
+            other => {
+                return Err(ParseActionError::UnknownOption {
+                    action: ACTION_ID,
+                    option: other.to_owned(),
+                });
+            }
+        };
+
+        let guild_id = interaction
+            .guild_id
+            .ok_or(ParseActionError::NotInAGuild { action: ACTION_ID })
+            .map(Into::into)?;
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            guild_id,
+            user_id: interaction.user.id.into(),
+            vote_nudges,
+        })
+    }
+}
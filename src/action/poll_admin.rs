@@ -0,0 +1,364 @@
+use std::time::Duration;
+
+use serenity::{
+    all::{CommandDataOption, CommandInteraction, CommandOptionType},
+    async_trait,
+    builder::{CreateCommand, CreateCommandOption, EditInteractionResponse},
+    model::prelude::Interaction,
+    prelude::Context,
+};
+use sqlx::PgPool;
+
+use crate::{
+    background_poll_handler::BackgroundPollHandler,
+    entities::{
+        AuditEvent, AuditEventKind, InvitePoll, InvitePollId, InvitePollNudge, InvitePollOutcome,
+        InvitePollReminder, InvitePollVoteSubmission, InvitePollWithVoteCount, PollRef,
+    },
+    error::Error,
+    resolve_option,
+    util::serenity::{GuildId, UserId},
+    POOL,
+};
+
+use super::{
+    util::{duplicate_invite_poll_error, is_manager},
+    Action, ParseActionError,
+};
+
+const ACTION_ID: &'static str = "poll-admin";
+const CLOSE_SUBCOMMAND_NAME: &'static str = "close";
+const EXTEND_SUBCOMMAND_NAME: &'static str = "extend";
+const REOPEN_SUBCOMMAND_NAME: &'static str = "reopen";
+const POLL_ID_OPTION_NAME: &'static str = "poll-id";
+const DURATION_OPTION_NAME: &'static str = "duration";
+
+#[derive(Debug)]
+enum PollAdminCommand {
+    /// Closes the poll immediately using the normal decision logic.
+    Close,
+    /// Pushes the end of the poll back.
+    Extend(Duration),
+    /// Clears the outcome of the poll, keeping it open for at least the given duration.
+    Reopen(Duration),
+}
+
+#[derive(Debug)]
+pub struct PollAdmin {
+    interaction: CommandInteraction,
+    guild_id: GuildId,
+    invite_poll_id: InvitePollId,
+    /// Administrator's Id
+    user_id: UserId,
+    command: PollAdminCommand,
+}
+
+impl PollAdmin {
+    /// Performs the command on a poll which passed the preliminary checks, returns how the poll
+    /// changed.
+    async fn perform(
+        &self,
+        ctx: &Context,
+        pool: &PgPool,
+        mut invite_poll: InvitePollWithVoteCount,
+    ) -> Result<&'static str, Error> {
+        let (event, content) = match &self.command {
+            PollAdminCommand::Close => {
+                // the forced close is audited along with the outcome
+                BackgroundPollHandler::close_invite_poll(
+                    ctx,
+                    pool,
+                    &mut invite_poll,
+                    Some(&self.user_id),
+                )
+                .await?;
+
+                return Ok("closed");
+            }
+            PollAdminCommand::Extend(duration) => {
+                let mut transaction = pool.begin().await?;
+                invite_poll
+                    .invite_poll
+                    .extend(&mut *transaction, duration)
+                    .await?;
+                let event = AuditEvent::create(
+                    &mut *transaction,
+                    &self.guild_id,
+                    AuditEventKind::PollExtended,
+                    Some(&self.user_id),
+                    Some(&PollRef::Invite(self.invite_poll_id.clone())),
+                    &format!("extended by {}", humantime::format_duration(*duration)),
+                )
+                .await?;
+                transaction.commit().await?;
+
+                (event, "extended")
+            }
+            PollAdminCommand::Reopen(duration) => {
+                let mut transaction = pool.begin().await?;
+                let existing = InvitePoll::find_open_by_invitee(
+                    &mut *transaction,
+                    &invite_poll.invite_poll.guild_id,
+                    &invite_poll.invite_poll.invitee,
+                )
+                .await?;
+                if let Some(existing) = existing {
+                    return Err(duplicate_invite_poll_error(&existing));
+                }
+
+                invite_poll
+                    .invite_poll
+                    .reopen(&mut *transaction, duration)
+                    .await?;
+
+                // the delegations are resolved again when the poll closes
+                InvitePollVoteSubmission::delete_inherited(&mut *transaction, &self.invite_poll_id)
+                    .await?;
+                InvitePollReminder::delete_by_invite_poll_id(
+                    &mut *transaction,
+                    &self.invite_poll_id,
+                )
+                .await?;
+                InvitePollNudge::delete_by_invite_poll_id(&mut *transaction, &self.invite_poll_id)
+                    .await?;
+                let event = AuditEvent::create(
+                    &mut *transaction,
+                    &self.guild_id,
+                    AuditEventKind::PollReopened,
+                    Some(&self.user_id),
+                    Some(&PollRef::Invite(self.invite_poll_id.clone())),
+                    &format!(
+                        "reopened for at least {}",
+                        humantime::format_duration(*duration)
+                    ),
+                )
+                .await?;
+                transaction.commit().await?;
+
+                (event, "reopened")
+            }
+        };
+
+        event.mirror(&ctx.http, pool).await;
+
+        let mut invite_poll = InvitePollWithVoteCount::find_by_id(pool, &self.invite_poll_id)
+            .await?
+            .ok_or_else(|| Error::InvitePollNotFound(self.invite_poll_id.to_owned()))?;
+        invite_poll.load_votes(pool).await?;
+        BackgroundPollHandler::update_poll_message(ctx, &invite_poll).await?;
+
+        Ok(content)
+    }
+}
+
+#[async_trait]
+impl Action for PollAdmin {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        // check permissions
+        let member = self.interaction.member.as_deref();
+        if !is_manager(pool, &self.guild_id, member).await? {
+            return Err(ParseActionError::InsufficientPermissions.into());
+        }
+
+        // load the poll, the polls of other guilds are out of reach of its managers
+        let invite_poll = InvitePollWithVoteCount::find_by_id(pool, &self.invite_poll_id)
+            .await?
+            .filter(|invite_poll| *invite_poll.invite_poll.guild_id == *self.guild_id)
+            .ok_or_else(|| Error::InvitePollNotFound(self.invite_poll_id.to_owned()))?;
+
+        // preliminary checks
+        match (&self.command, invite_poll.invite_poll.outcome) {
+            (PollAdminCommand::Close | PollAdminCommand::Extend(_), Some(_)) => {
+                return Err(Error::InvitePollClosed(self.invite_poll_id.to_owned()));
+            }
+            (PollAdminCommand::Reopen(_), None) => {
+                return Err(Error::InvitePollOpen(self.invite_poll_id.to_owned()));
+            }
+            // the invite of an allowed poll has already been sent
+            (PollAdminCommand::Reopen(_), Some(InvitePollOutcome::Allow)) => {
+                return Err(Error::CannotReopenAllowedInvitePoll(
+                    self.invite_poll_id.to_owned(),
+                ));
+            }
+            _ => {}
+        }
+
+        // closing the poll can take a while, the result is reported through the deferred response
+        self.interaction.defer_ephemeral(&ctx.http).await?;
+
+        let res = self.perform(ctx, pool, invite_poll).await;
+        let content = match &res {
+            Ok(content) => format!("The poll `{}` has been {}.", self.invite_poll_id, content),
+            Err(err) if err.is_client_error() => format!("Could not perform action: {}.", err),
+            Err(_) => "Could not perform action.".to_owned(),
+        };
+
+        self.interaction
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await?;
+
+        match res {
+            Err(err) if !err.is_client_error() => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn register() -> Vec<CreateCommand> {
+        let poll_id_option = CreateCommandOption::new(
+            CommandOptionType::String,
+            POLL_ID_OPTION_NAME,
+            "The id of the invite poll",
+        )
+        .required(true);
+
+        vec![CreateCommand::new(ACTION_ID)
+            .description("Manages invite polls")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    CLOSE_SUBCOMMAND_NAME,
+                    "Closes a poll immediately",
+                )
+                .add_sub_option(poll_id_option.clone()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    EXTEND_SUBCOMMAND_NAME,
+                    "Extends the duration of an open poll",
+                )
+                .add_sub_option(poll_id_option.clone())
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        DURATION_OPTION_NAME,
+                        "How much longer the poll should last",
+                    )
+                    .required(true),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    REOPEN_SUBCOMMAND_NAME,
+                    "Reopens a closed poll",
+                )
+                .add_sub_option(poll_id_option)
+                .add_sub_option(CreateCommandOption::new(
+                    CommandOptionType::String,
+                    DURATION_OPTION_NAME,
+                    "How long the poll should stay open at least",
+                )),
+            )]
+    }
+}
+
+fn parse_subcommand_options(
+    options: &[CommandDataOption],
+) -> Result<(Option<InvitePollId>, Option<Duration>), ParseActionError> {
+    let mut invite_poll_id: Option<InvitePollId> = None;
+    let mut duration: Option<Duration> = None;
+
+    for opt in options {
+        match opt.name.as_str() {
+            name @ POLL_ID_OPTION_NAME => {
+                let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                let value = value.parse::<InvitePollId>().map_err(|err| {
+                    ParseActionError::InvalidOptionValue {
+                        action: ACTION_ID,
+                        option: name.into(),
+                        value: value.to_string(),
+                        source: Box::new(err),
+                    }
+                })?;
+                invite_poll_id = Some(value);
+            }
+            name @ DURATION_OPTION_NAME => {
+                let value = resolve_option!(ACTION_ID, &opt.value, String, name)?;
+                let value = humantime::parse_duration(value).map_err(|err| {
+                    ParseActionError::InvalidOptionValue {
+                        action: ACTION_ID,
+                        option: name.into(),
+                        value: value.to_string(),
+                        source: Box::new(err),
+                    }
+                })?;
+                duration = Some(value);
+            }
+            other => {
+                return Err(ParseActionError::UnknownOption {
+                    action: ACTION_ID,
+                    option: other.to_owned(),
+                });
+            }
+        }
+    }
+
+    Ok((invite_poll_id, duration))
+}
+
+impl<'a> TryFrom<&'a Interaction> for PollAdmin {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_command()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if interaction.data.name != ACTION_ID {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        // subcommand
+        let subcommand =
+            interaction
+                .data
+                .options
+                .first()
+                .ok_or(ParseActionError::MissingOption {
+                    action: ACTION_ID,
+                    option: "subcommand".into(),
+                })?;
+        let options = resolve_option!(ACTION_ID, &subcommand.value, SubCommand, &subcommand.name)?;
+        let (invite_poll_id, duration) = parse_subcommand_options(options)?;
+
+        let invite_poll_id = invite_poll_id.ok_or(ParseActionError::MissingOption {
+            action: ACTION_ID,
+            option: POLL_ID_OPTION_NAME.into(),
+        })?;
+
+        let command = match subcommand.name.as_str() {
+            CLOSE_SUBCOMMAND_NAME => PollAdminCommand::Close,
+            EXTEND_SUBCOMMAND_NAME => {
+                PollAdminCommand::Extend(duration.ok_or(ParseActionError::MissingOption {
+                    action: ACTION_ID,
+                    option: DURATION_OPTION_NAME.into(),
+                })?)
+            }
+            REOPEN_SUBCOMMAND_NAME => {
+                PollAdminCommand::Reopen(duration.unwrap_or(Duration::from_secs(24 * 60 * 60)))
+                // 1 day
+            }
+            other => {
+                return Err(ParseActionError::UnknownOption {
+                    action: ACTION_ID,
+                    option: other.to_owned(),
+                });
+            }
+        };
+
+        let guild_id = interaction
+            .guild_id
+            .ok_or(ParseActionError::NotInAGuild { action: ACTION_ID })
+            .map(Into::into)?;
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            guild_id,
+            invite_poll_id,
+            user_id: interaction.user.id.into(),
+            command,
+        })
+    }
+}
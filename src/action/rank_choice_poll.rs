@@ -0,0 +1,285 @@
+use serenity::{
+    all::{ButtonStyle, ComponentInteraction, ComponentInteractionDataKind},
+    async_trait,
+    builder::{
+        CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+        CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    },
+    model::prelude::Interaction,
+    prelude::Context,
+};
+
+use crate::{
+    background_poll_handler::BackgroundPollHandler,
+    entities::{ChoicePollId, ChoicePollRanking, ChoicePollWithVoteCount},
+    error::Error,
+    util::serenity::UserId,
+    POOL,
+};
+
+use super::{util::parse_poll_id_field, Action, ParseActionError};
+
+const ACTION_ID: &'static str = "democracy.choice-poll-rank";
+const SUBMIT_SUFFIX: &'static str = "submit";
+
+/// The radix used to encode the picked positions in the custom id of the components, so that
+/// every position (0 - 24) takes a single character and the id stays below Discord's limit.
+const PICKS_RADIX: u32 = 36;
+
+#[derive(Debug)]
+enum RankStep {
+    /// The voter pressed the button on the poll message.
+    Start,
+    /// The voter picked their next preferred option.
+    Pick { picks: Vec<i16> },
+    /// The voter submitted their ranking before ranking every option.
+    Submit { picks: Vec<i16> },
+}
+
+/// Collects the ranking of a ranked-choice poll through a sequence of ephemeral select menus,
+/// each listing the options which have not been ranked yet.
+#[derive(Debug)]
+pub struct RankChoicePoll {
+    interaction: ComponentInteraction,
+    choice_poll_id: ChoicePollId,
+    /// Submitter's Id
+    user_id: UserId,
+    step: RankStep,
+}
+
+fn encode_picks(picks: &[i16]) -> String {
+    picks
+        .iter()
+        .filter_map(|pick| char::from_digit(*pick as u32, PICKS_RADIX))
+        .collect()
+}
+
+fn decode_picks(value: &str) -> Option<Vec<i16>> {
+    value
+        .chars()
+        .map(|c| c.to_digit(PICKS_RADIX).map(|pick| pick as i16))
+        .collect()
+}
+
+impl RankChoicePoll {
+    fn render_ranking(choice_poll: &ChoicePollWithVoteCount, picks: &[i16]) -> String {
+        picks
+            .iter()
+            .enumerate()
+            .map(|(rank, position)| {
+                format!(
+                    "**{}.** {}",
+                    rank + 1,
+                    choice_poll.label(*position).unwrap_or_default()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn render_step(
+        &self,
+        choice_poll: &ChoicePollWithVoteCount,
+        picks: &[i16],
+    ) -> CreateInteractionResponseMessage {
+        let id = format!(
+            "{}.{}.{}",
+            ACTION_ID,
+            self.choice_poll_id,
+            encode_picks(picks)
+        );
+
+        let remaining = choice_poll
+            .options
+            .iter()
+            .filter(|opt| !picks.contains(&opt.option.position))
+            .map(|opt| {
+                CreateSelectMenuOption::new(&opt.option.label, opt.option.position.to_string())
+            })
+            .collect();
+
+        let content = if picks.is_empty() {
+            "Pick your most preferred choice.".to_owned()
+        } else {
+            format!(
+                "{}\n\nPick your next preferred choice or submit your ranking.",
+                Self::render_ranking(choice_poll, picks)
+            )
+        };
+
+        CreateInteractionResponseMessage::default()
+            .ephemeral(true)
+            .content(content)
+            .components(vec![
+                CreateActionRow::SelectMenu(
+                    CreateSelectMenu::new(&id, CreateSelectMenuKind::String { options: remaining })
+                        .placeholder(format!("Choice #{}", picks.len() + 1)),
+                ),
+                CreateActionRow::Buttons(vec![CreateButton::new(format!(
+                    "{}.{}",
+                    id, SUBMIT_SUFFIX
+                ))
+                .label("Submit Ranking")
+                .style(ButtonStyle::Success)
+                .disabled(picks.is_empty())]),
+            ])
+    }
+
+    async fn submit(
+        &self,
+        ctx: &Context,
+        choice_poll: ChoicePollWithVoteCount,
+        picks: &[i16],
+    ) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+        let mut transaction = pool.begin().await?;
+
+        // submit the ranking
+        let _rankings = ChoicePollRanking::replace(
+            &mut transaction,
+            &self.choice_poll_id,
+            &self.user_id,
+            picks,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        // re-render the poll message
+        let updated = ChoicePollWithVoteCount::find_by_id(pool, &self.choice_poll_id)
+            .await?
+            .ok_or_else(|| Error::ChoicePollNotFound(self.choice_poll_id.to_owned()))?;
+
+        BackgroundPollHandler::update_poll_message(ctx, &updated).await?;
+
+        self.interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::default()
+                        .content(format!(
+                            "Your ranking has been submitted:\n{}",
+                            Self::render_ranking(&choice_poll, picks)
+                        ))
+                        .components(Vec::new()),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Action for RankChoicePoll {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        // load the poll
+        let choice_poll = ChoicePollWithVoteCount::find_by_id(pool, &self.choice_poll_id)
+            .await?
+            .ok_or_else(|| Error::ChoicePollNotFound(self.choice_poll_id.to_owned()))?;
+
+        // the ephemeral messages outlive the buttons on the poll message
+        if choice_poll.choice_poll.outcome.is_some() {
+            return Err(Error::ChoicePollClosed(self.choice_poll_id.to_owned()));
+        }
+
+        match &self.step {
+            RankStep::Start => {
+                self.interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Message(self.render_step(&choice_poll, &[])),
+                    )
+                    .await?;
+            }
+            RankStep::Pick { picks } if picks.len() >= choice_poll.options.len() => {
+                self.submit(ctx, choice_poll, picks).await?;
+            }
+            RankStep::Pick { picks } => {
+                self.interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::UpdateMessage(
+                            self.render_step(&choice_poll, picks),
+                        ),
+                    )
+                    .await?;
+            }
+            RankStep::Submit { picks } => {
+                self.submit(ctx, choice_poll, picks).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for RankChoicePoll {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_message_component()
+            .ok_or(ParseActionError::MismatchedAction)?;
+
+        let id = &interaction.data.custom_id;
+        let invalid_id = || ParseActionError::InvalidActionId {
+            action: ACTION_ID,
+            id: id.clone(),
+            source: None,
+        };
+
+        // the button on the poll message carries no state, the poll id is read from its embed
+        if id == ACTION_ID {
+            return Ok(Self {
+                interaction: interaction.clone(),
+                choice_poll_id: parse_poll_id_field::<ChoicePollId>(interaction)?,
+                user_id: UserId::from(interaction.user.id),
+                step: RankStep::Start,
+            });
+        }
+
+        // the ephemeral components are of the form `<action>.<poll id>.<picks>[.submit]`
+        let state = match id.strip_prefix([ACTION_ID, "."].concat().as_str()) {
+            Some(state) => state,
+            None => return Err(ParseActionError::MismatchedAction),
+        };
+
+        let mut parts = state.split('.');
+        let choice_poll_id = parts
+            .next()
+            .ok_or_else(invalid_id)?
+            .parse::<ChoicePollId>()
+            .map_err(|err| ParseActionError::InvalidActionId {
+                action: ACTION_ID,
+                id: id.clone(),
+                source: Some(Box::new(err)),
+            })?;
+        let mut picks = parts.next().and_then(decode_picks).ok_or_else(invalid_id)?;
+
+        let step = match (parts.next(), &interaction.data.kind) {
+            (Some(SUBMIT_SUFFIX), _) => RankStep::Submit { picks },
+            (None, ComponentInteractionDataKind::StringSelect { values }) => {
+                let pick = values
+                    .first()
+                    .and_then(|value| value.parse::<i16>().ok())
+                    .ok_or_else(invalid_id)?;
+                if !picks.contains(&pick) {
+                    picks.push(pick);
+                }
+
+                RankStep::Pick { picks }
+            }
+            _ => return Err(invalid_id()),
+        };
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            choice_poll_id,
+            user_id: UserId::from(interaction.user.id),
+            step,
+        })
+    }
+}
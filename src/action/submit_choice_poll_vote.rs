@@ -0,0 +1,113 @@
+use serenity::{
+    all::{ComponentInteraction, ComponentInteractionDataKind},
+    async_trait,
+    builder::CreateInteractionResponseMessage,
+    model::prelude::Interaction,
+    prelude::Context,
+};
+
+use crate::{
+    entities::{ChoicePollId, ChoicePollVoteSubmission, ChoicePollWithVoteCount},
+    error::Error,
+    util::serenity::UserId,
+    POOL,
+};
+
+use super::{util::parse_poll_id_field, Action, ParseActionError};
+
+const ACTION_ID: &'static str = "democracy.choice-poll-vote";
+
+#[derive(Debug)]
+pub struct SubmitChoicePollVote {
+    interaction: ComponentInteraction,
+    choice_poll_id: ChoicePollId,
+    /// Submitter's Id
+    user_id: UserId,
+    /// Position of the chosen option
+    position: i16,
+}
+
+#[async_trait]
+impl Action for SubmitChoicePollVote {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        // submit the vote
+        let _choice_poll_vote_submission = ChoicePollVoteSubmission::create_or_update(
+            pool,
+            &self.choice_poll_id,
+            &self.user_id,
+            self.position,
+        )
+        .await?;
+
+        // load the poll
+        let choice_poll = ChoicePollWithVoteCount::find_by_id(pool, &self.choice_poll_id)
+            .await?
+            .ok_or_else(|| Error::ChoicePollNotFound(self.choice_poll_id.to_owned()))?;
+
+        // re-render message
+        let renderer = choice_poll.create_renderer(ctx.clone()).await?;
+        self.interaction
+            .create_response(
+                &ctx.http,
+                serenity::builder::CreateInteractionResponse::UpdateMessage(
+                    renderer.render_create_interaction_response_data(
+                        CreateInteractionResponseMessage::default(),
+                    ),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for SubmitChoicePollVote {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_message_component()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if !interaction.data.custom_id.starts_with(ACTION_ID) {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        let choice_poll_id = parse_poll_id_field::<ChoicePollId>(interaction)?;
+
+        let position = {
+            let id = &interaction.data.custom_id;
+
+            // buttons carry the position in their id, select menus in their value
+            let position = match &interaction.data.kind {
+                ComponentInteractionDataKind::StringSelect { values } => values.first(),
+                _ => None,
+            }
+            .map(String::as_str)
+            .or_else(|| id.strip_prefix([ACTION_ID, "."].concat().as_str()))
+            .ok_or(ParseActionError::InvalidActionId {
+                action: ACTION_ID,
+                id: id.clone(),
+                source: None,
+            })?;
+
+            position
+                .parse::<i16>()
+                .map_err(|err| ParseActionError::InvalidActionId {
+                    action: ACTION_ID,
+                    id: id.clone(),
+                    source: Some(Box::new(err)),
+                })?
+        };
+
+        let user_id = UserId::from(interaction.user.id);
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            choice_poll_id,
+            user_id,
+            position,
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use serenity::{
+    all::ComponentInteraction, async_trait, builder::CreateInteractionResponseMessage,
+    model::prelude::Interaction, prelude::Context,
+};
+
+use crate::{
+    entities::{
+        CommunityPollId, CommunityPollVoteSubmission, CommunityPollWithVoteCount, PollVote,
+    },
+    error::Error,
+    util::serenity::UserId,
+    POOL,
+};
+
+use super::{
+    util::{parse_poll_id_field, parse_vote},
+    Action, ParseActionError,
+};
+
+const ACTION_ID: &'static str = "democracy.community-poll-vote";
+
+#[derive(Debug)]
+pub struct SubmitCommunityPollVote {
+    interaction: ComponentInteraction,
+    community_poll_id: CommunityPollId,
+    /// Submitter's Id
+    user_id: UserId,
+    vote: PollVote,
+}
+
+#[async_trait]
+impl Action for SubmitCommunityPollVote {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        // submit the vote
+        let _community_poll_vote_submission = CommunityPollVoteSubmission::create_or_update(
+            pool,
+            &self.community_poll_id,
+            &self.user_id,
+            self.vote,
+        )
+        .await?;
+
+        // load the poll
+        let community_poll = CommunityPollWithVoteCount::find_by_id(pool, &self.community_poll_id)
+            .await?
+            .ok_or_else(|| Error::CommunityPollNotFound(self.community_poll_id.to_owned()))?;
+
+        // re-render message
+        let renderer = community_poll.create_renderer(ctx.clone()).await?;
+        self.interaction
+            .create_response(
+                &ctx.http,
+                serenity::builder::CreateInteractionResponse::UpdateMessage(
+                    renderer.render_create_interaction_response_data(
+                        CreateInteractionResponseMessage::default(),
+                    ),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for SubmitCommunityPollVote {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_message_component()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if !interaction.data.custom_id.starts_with(ACTION_ID) {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        let community_poll_id = parse_poll_id_field::<CommunityPollId>(interaction)?;
+        let vote = parse_vote(ACTION_ID, interaction)?;
+
+        let user_id = UserId::from(interaction.user.id);
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            community_poll_id,
+            user_id,
+            vote,
+        })
+    }
+}
@@ -1,16 +1,32 @@
 use serenity::{
-    all::ComponentInteraction, async_trait, builder::CreateInteractionResponseMessage,
-    model::prelude::Interaction, prelude::Context,
+    all::{ComponentInteraction, InputTextStyle},
+    async_trait,
+    builder::{
+        CreateActionRow, CreateInputText, CreateInteractionResponse,
+        CreateInteractionResponseMessage, CreateModal, EditMessage,
+    },
+    model::prelude::Interaction,
+    prelude::Context,
 };
 
 use crate::{
-    entities::{InvitePollId, InvitePollVote, InvitePollVoteSubmission, InvitePollWithVoteCount},
+    background_poll_handler::BackgroundPollHandler,
+    entities::{
+        vote_weight, Guild, GuildVoteWeight, InvitePoll, InvitePollId, InvitePollVoteEvent,
+        InvitePollVoteSubmission, InvitePollWithVoteCount, PollVote,
+    },
     error::Error,
-    util::serenity::UserId,
+    util::{
+        serenity::{GuildId, UserId},
+        DiscordTimestamp, DiscordTimestampStyle,
+    },
     POOL,
 };
 
-use super::{Action, ParseActionError, ParseParentMessageError};
+use super::{
+    util::{parse_poll_id_field, parse_vote},
+    Action, ParseActionError, INVITE_POLL_VOTE_REASON_ACTION_ID, REASON_INPUT_ID,
+};
 
 const ACTION_ID: &'static str = "democracy.invite-poll-vote";
 pub const POLL_ID_FIELD_NAME: &'static str = "Poll Id";
@@ -18,10 +34,11 @@ pub const POLL_ID_FIELD_NAME: &'static str = "Poll Id";
 #[derive(Debug)]
 pub struct SubmitInvitePollVote {
     interaction: ComponentInteraction,
+    guild_id: GuildId,
     invite_poll_id: InvitePollId,
     /// Submitter's Id
     user_id: UserId,
-    vote: InvitePollVote,
+    vote: PollVote,
 }
 
 #[async_trait]
@@ -29,31 +46,144 @@ impl Action for SubmitInvitePollVote {
     async fn execute(&self, ctx: &Context) -> Result<(), Error> {
         let pool = POOL.get().expect("the Pool to be initialized");
 
-        // submit the vote
-        let _invite_poll_vote_submission = InvitePollVoteSubmission::create_or_update(
-            pool,
+        // preliminary checks
+        let invite_poll = InvitePollWithVoteCount::find_by_id(pool, &self.invite_poll_id)
+            .await?
+            .ok_or_else(|| Error::InvitePollNotFound(self.invite_poll_id.to_owned()))?;
+        if invite_poll.invite_poll.outcome.is_some() {
+            return Err(Error::InvitePollClosed(self.invite_poll_id.to_owned()));
+        }
+
+        let settings = Guild::find_by_id(pool, &self.guild_id)
+            .await?
+            .ok_or_else(|| Error::GuildNotFound(self.guild_id.clone()))?;
+        let member = self
+            .interaction
+            .member
+            .as_ref()
+            .ok_or(Error::MissingVoterRole)?;
+        if !settings.has_voter_role(member) {
+            return Err(Error::MissingVoterRole);
+        }
+        if let Some(allowed_at) = settings.voting_allowed_at(member) {
+            return Err(Error::InsufficientTenure(DiscordTimestamp::new(
+                allowed_at,
+                DiscordTimestampStyle::Relative,
+            )));
+        }
+
+        // submit the vote, recording it in the vote history
+        let weights = GuildVoteWeight::find_by_guild_id(pool, &self.guild_id).await?;
+        let weight = vote_weight(&weights, member);
+
+        let mut transaction = pool.begin().await?;
+        // the poll may have been closed since it was loaded
+        InvitePoll::find_open_for_share(&mut *transaction, &self.invite_poll_id)
+            .await?
+            .ok_or_else(|| Error::InvitePollClosed(self.invite_poll_id.to_owned()))?;
+        // the insert of a first vote locks the new submission, a changed vote locks the existing
+        // one before replacing it, unless the guild does not allow changing votes
+        let submission = InvitePollVoteSubmission::create(
+            &mut *transaction,
             &self.invite_poll_id,
             &self.user_id,
             self.vote,
+            weight,
         )
         .await?;
+        let (submission, previous_vote) = match submission {
+            Some(submission) => (submission, None),
+            None => {
+                let previous =
+                    InvitePollVoteSubmission::find_by_invite_poll_id_and_user_id_for_update(
+                        &mut *transaction,
+                        &self.invite_poll_id,
+                        &self.user_id,
+                    )
+                    .await?
+                    .ok_or(sqlx::Error::RowNotFound)?;
+                if !settings.allow_vote_changes {
+                    return Err(Error::VoteAlreadyCast(previous.vote));
+                }
+
+                let submission = InvitePollVoteSubmission::create_or_update(
+                    &mut *transaction,
+                    &self.invite_poll_id,
+                    &self.user_id,
+                    self.vote,
+                    weight,
+                )
+                .await?;
+
+                (submission, Some(previous.vote))
+            }
+        };
+        InvitePollVoteEvent::create(&mut *transaction, &submission, previous_vote).await?;
+        transaction.commit().await?;
 
-        // load the poll
-        let invite_poll = InvitePollWithVoteCount::find_by_id(pool, &self.invite_poll_id)
+        // reload the poll
+        let mut invite_poll = InvitePollWithVoteCount::find_by_id(pool, &self.invite_poll_id)
             .await?
             .ok_or_else(|| Error::InvitePollNotFound(self.invite_poll_id.to_owned()))?;
+        invite_poll.load_votes(pool).await?;
 
         // re-render message
         let renderer = invite_poll.create_renderer(ctx.clone()).await?;
-        self.interaction
-            .create_response(
-                &ctx.http,
-                serenity::builder::CreateInteractionResponse::UpdateMessage(
-                    renderer.render_create_interaction_response_data(
-                        CreateInteractionResponseMessage::default(),
-                    ),
-                ),
-            )
+        match self.vote {
+            // ask the voter for an optional reason, the vote is already recorded if they dismiss it
+            PollVote::No => {
+                self.interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Modal(
+                            CreateModal::new(
+                                format!(
+                                    "{}.{}",
+                                    INVITE_POLL_VOTE_REASON_ACTION_ID, self.invite_poll_id
+                                ),
+                                "Reason",
+                            )
+                            .components(vec![
+                                CreateActionRow::InputText(
+                                    CreateInputText::new(
+                                        InputTextStyle::Paragraph,
+                                        "Why are you voting no? (optional)",
+                                        REASON_INPUT_ID,
+                                    )
+                                    .required(false)
+                                    .max_length(200),
+                                ),
+                            ]),
+                        ),
+                    )
+                    .await?;
+
+                self.interaction
+                    .channel_id
+                    .edit_message(
+                        &ctx.http,
+                        self.interaction.message.id,
+                        renderer.render_edit_message(EditMessage::default()),
+                    )
+                    .await?;
+            }
+            _ => {
+                self.interaction
+                    .create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::UpdateMessage(
+                            renderer.render_create_interaction_response_data(
+                                CreateInteractionResponseMessage::default(),
+                            ),
+                        ),
+                    )
+                    .await?;
+            }
+        }
+
+        // the poll is closed right away if the remaining votes cannot change its outcome, a reason
+        // submitted afterwards is rejected
+        BackgroundPollHandler::close_invite_poll_if_decided(ctx, pool, &self.invite_poll_id)
             .await?;
 
         Ok(())
@@ -71,55 +201,19 @@ impl<'a> TryFrom<&'a Interaction> for SubmitInvitePollVote {
             return Err(ParseActionError::MismatchedAction);
         }
 
-        let invite_poll_id = {
-            let field = interaction
-                .message
-                .embeds
-                .iter()
-                .flat_map(|embed| embed.fields.iter())
-                .find(|field| field.name == POLL_ID_FIELD_NAME)
-                .ok_or(ParseParentMessageError::FieldNotFound {
-                    field: POLL_ID_FIELD_NAME.into(),
-                })?;
-
-            let val = field.value.as_str();
-            let val = val
-                .strip_prefix('`')
-                .unwrap_or(val)
-                .strip_suffix('`')
-                .unwrap_or(val);
-
-            val.parse::<InvitePollId>()
-                .map_err(|err| ParseParentMessageError::InvalidField {
-                    field: POLL_ID_FIELD_NAME.into(),
-                    value: val.into(),
-                    source: Box::new(err),
-                })?
-        };
-
-        let vote = {
-            let vote = &interaction.data.custom_id;
-
-            let vote = vote
-                .strip_prefix([ACTION_ID, "."].concat().as_str())
-                .ok_or(ParseActionError::InvalidActionId {
-                    action: ACTION_ID,
-                    id: vote.clone(),
-                    source: None,
-                })?;
-
-            vote.parse::<InvitePollVote>()
-                .map_err(|err| ParseActionError::InvalidActionId {
-                    action: ACTION_ID,
-                    id: vote.to_string(),
-                    source: Some(Box::new(err)),
-                })?
-        };
+        let invite_poll_id = parse_poll_id_field::<InvitePollId>(interaction)?;
+        let vote = parse_vote(ACTION_ID, interaction)?;
 
         let user_id = UserId::from(interaction.user.id);
 
+        let guild_id = interaction
+            .guild_id
+            .ok_or(ParseActionError::NotInAGuild { action: ACTION_ID })
+            .map(Into::into)?;
+
         Ok(Self {
             interaction: interaction.clone(),
+            guild_id,
             invite_poll_id,
             user_id,
             vote,
@@ -0,0 +1,115 @@
+use serenity::{
+    all::{ActionRowComponent, ModalInteraction},
+    async_trait,
+    builder::{CreateInteractionResponse, CreateInteractionResponseMessage},
+    model::prelude::Interaction,
+    prelude::Context,
+};
+
+use crate::{
+    entities::{InvitePollId, InvitePollVoteSubmission, InvitePollWithVoteCount},
+    error::Error,
+    util::serenity::UserId,
+    POOL,
+};
+
+use super::{Action, ParseActionError};
+
+pub const INVITE_POLL_VOTE_REASON_ACTION_ID: &'static str = "democracy.invite-poll-vote-reason";
+pub const REASON_INPUT_ID: &'static str = "reason";
+
+/// Submission of the modal asking for the reason of a no vote.
+#[derive(Debug)]
+pub struct SubmitInvitePollVoteReason {
+    interaction: ModalInteraction,
+    invite_poll_id: InvitePollId,
+    /// Submitter's Id
+    user_id: UserId,
+    reason: Option<String>,
+}
+
+#[async_trait]
+impl Action for SubmitInvitePollVoteReason {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        let invite_poll = InvitePollWithVoteCount::find_by_id(pool, &self.invite_poll_id)
+            .await?
+            .ok_or_else(|| Error::InvitePollNotFound(self.invite_poll_id.to_owned()))?;
+        if invite_poll.invite_poll.outcome.is_some() {
+            return Err(Error::InvitePollClosed(self.invite_poll_id.to_owned()));
+        }
+
+        let submission = InvitePollVoteSubmission::update_reason(
+            pool,
+            &self.invite_poll_id,
+            &self.user_id,
+            self.reason.as_deref(),
+        )
+        .await?;
+
+        let content = match (submission, &self.reason) {
+            (Some(_), Some(_)) => "Your vote and reason have been recorded.",
+            (Some(_), None) => "Your vote has been recorded.",
+            (None, _) => "Your vote is no longer no, the reason has been discarded.",
+        };
+
+        self.interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::default()
+                        .ephemeral(true)
+                        .content(content),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for SubmitInvitePollVoteReason {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_modal_submit()
+            .ok_or(ParseActionError::MismatchedAction)?;
+
+        let custom_id = &interaction.data.custom_id;
+        let invite_poll_id = custom_id
+            .strip_prefix(INVITE_POLL_VOTE_REASON_ACTION_ID)
+            .and_then(|id| id.strip_prefix('.'))
+            .ok_or(ParseActionError::MismatchedAction)?;
+        let invite_poll_id = invite_poll_id.parse::<InvitePollId>().map_err(|err| {
+            ParseActionError::InvalidActionId {
+                action: INVITE_POLL_VOTE_REASON_ACTION_ID,
+                id: custom_id.clone(),
+                source: Some(Box::new(err)),
+            }
+        })?;
+
+        let reason = interaction
+            .data
+            .components
+            .iter()
+            .flat_map(|row| row.components.iter())
+            .find_map(|component| match component {
+                ActionRowComponent::InputText(input) if input.custom_id == REASON_INPUT_ID => {
+                    input.value.as_deref()
+                }
+                _ => None,
+            })
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty())
+            .map(ToOwned::to_owned);
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            invite_poll_id,
+            user_id: interaction.user.id.into(),
+            reason,
+        })
+    }
+}
@@ -0,0 +1,96 @@
+use serenity::{
+    all::ComponentInteraction, async_trait, builder::CreateInteractionResponseMessage,
+    model::prelude::Interaction, prelude::Context,
+};
+
+use crate::{
+    entities::{KickPollId, KickPollVoteSubmission, KickPollWithVoteCount, PollVote},
+    error::Error,
+    util::serenity::UserId,
+    POOL,
+};
+
+use super::{
+    util::{parse_poll_id_field, parse_vote},
+    Action, ParseActionError,
+};
+
+const ACTION_ID: &'static str = "democracy.kick-poll-vote";
+
+#[derive(Debug)]
+pub struct SubmitKickPollVote {
+    interaction: ComponentInteraction,
+    kick_poll_id: KickPollId,
+    /// Submitter's Id
+    user_id: UserId,
+    vote: PollVote,
+}
+
+#[async_trait]
+impl Action for SubmitKickPollVote {
+    async fn execute(&self, ctx: &Context) -> Result<(), Error> {
+        let pool = POOL.get().expect("the Pool to be initialized");
+
+        // the target is neither a voter nor counted toward the quorum
+        let kick_poll = KickPollWithVoteCount::find_by_id(pool, &self.kick_poll_id)
+            .await?
+            .ok_or_else(|| Error::KickPollNotFound(self.kick_poll_id.to_owned()))?;
+        if kick_poll.kick_poll.target.get() == self.user_id.get() {
+            return Err(Error::CannotVoteOnOwnKick);
+        }
+
+        // submit the vote
+        let _kick_poll_vote_submission = KickPollVoteSubmission::create_or_update(
+            pool,
+            &self.kick_poll_id,
+            &self.user_id,
+            self.vote,
+        )
+        .await?;
+
+        // load the poll
+        let kick_poll = KickPollWithVoteCount::find_by_id(pool, &self.kick_poll_id)
+            .await?
+            .ok_or_else(|| Error::KickPollNotFound(self.kick_poll_id.to_owned()))?;
+
+        // re-render message
+        let renderer = kick_poll.create_renderer(ctx.clone()).await?;
+        self.interaction
+            .create_response(
+                &ctx.http,
+                serenity::builder::CreateInteractionResponse::UpdateMessage(
+                    renderer.render_create_interaction_response_data(
+                        CreateInteractionResponseMessage::default(),
+                    ),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a Interaction> for SubmitKickPollVote {
+    type Error = ParseActionError;
+
+    fn try_from(value: &'a Interaction) -> Result<Self, Self::Error> {
+        let interaction = value
+            .as_message_component()
+            .ok_or(ParseActionError::MismatchedAction)?;
+        if !interaction.data.custom_id.starts_with(ACTION_ID) {
+            return Err(ParseActionError::MismatchedAction);
+        }
+
+        let kick_poll_id = parse_poll_id_field::<KickPollId>(interaction)?;
+        let vote = parse_vote(ACTION_ID, interaction)?;
+
+        let user_id = UserId::from(interaction.user.id);
+
+        Ok(Self {
+            interaction: interaction.clone(),
+            kick_poll_id,
+            user_id,
+            vote,
+        })
+    }
+}
@@ -1,3 +1,16 @@
+use std::str::FromStr;
+
+use serenity::{all::ComponentInteraction, model::guild::Member};
+use sqlx::{Executor, Postgres};
+
+use crate::{
+    entities::{Guild, InvitePoll, PollVote},
+    error::Error,
+    util::serenity::GuildId,
+};
+
+use super::{ParseActionError, ParseParentMessageError, POLL_ID_FIELD_NAME};
+
 #[macro_export(local_inner_macros)]
 macro_rules! create_actions {
     ($name:ident, $($var:ident),+) => {
@@ -63,3 +76,99 @@ macro_rules! resolve_option {
         }
     }};
 }
+
+/// Parses the poll id stored in the embed of the message a component is attached to.
+pub fn parse_poll_id_field<T>(interaction: &ComponentInteraction) -> Result<T, ParseActionError>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let field = interaction
+        .message
+        .embeds
+        .iter()
+        .flat_map(|embed| embed.fields.iter())
+        .find(|field| field.name == POLL_ID_FIELD_NAME)
+        .ok_or(ParseParentMessageError::FieldNotFound {
+            field: POLL_ID_FIELD_NAME.into(),
+        })?;
+
+    let val = field.value.as_str();
+    let val = val
+        .strip_prefix('`')
+        .unwrap_or(val)
+        .strip_suffix('`')
+        .unwrap_or(val);
+
+    let id = val
+        .parse::<T>()
+        .map_err(|err| ParseParentMessageError::InvalidField {
+            field: POLL_ID_FIELD_NAME.into(),
+            value: val.into(),
+            source: Box::new(err),
+        })?;
+
+    Ok(id)
+}
+
+/// Whether `member` can manage the bot, either as an administrator or through the guild's manager
+/// role.
+pub async fn is_manager<'c, E>(
+    executor: E,
+    guild_id: &GuildId,
+    member: Option<&Member>,
+) -> Result<bool, Error>
+where
+    E: Executor<'c, Database = Postgres>,
+{
+    let member = match member {
+        Some(member) => member,
+        None => return Ok(false),
+    };
+
+    if member
+        .permissions
+        .is_some_and(|permissions| permissions.administrator())
+    {
+        return Ok(true);
+    }
+
+    let manager_role_id = Guild::find_by_id(executor, guild_id)
+        .await?
+        .and_then(|guild| guild.manager_role_id);
+
+    Ok(manager_role_id.is_some_and(|role_id| member.roles.contains(&role_id)))
+}
+
+/// The error returned when an invite poll cannot be opened because of the `existing` one.
+pub fn duplicate_invite_poll_error(existing: &InvitePoll) -> Error {
+    Error::DuplicateInvitePoll(
+        existing.invitee.clone(),
+        existing
+            .message_url()
+            .unwrap_or_else(|| format!("`{}`", existing.id)),
+    )
+}
+
+/// Parses the vote from the custom id of a component of the form `<action>.<vote>`.
+pub fn parse_vote(
+    action: &'static str,
+    interaction: &ComponentInteraction,
+) -> Result<PollVote, ParseActionError> {
+    let vote = &interaction.data.custom_id;
+
+    let vote = vote.strip_prefix([action, "."].concat().as_str()).ok_or(
+        ParseActionError::InvalidActionId {
+            action,
+            id: vote.clone(),
+            source: None,
+        },
+    )?;
+
+    vote.parse::<PollVote>()
+        .map_err(|err| ParseActionError::InvalidActionId {
+            action,
+            id: vote.to_string(),
+            source: Some(Box::new(err)),
+        })
+}
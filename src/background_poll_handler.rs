@@ -1,32 +1,82 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use serenity::{
-    builder::{CreateInvite, CreateMessage, EditMessage},
-    model::prelude::UserId,
+    builder::{CreateAllowedMentions, CreateInvite, CreateMessage, EditMessage},
+    http::Http,
+    model::{guild::PartialGuild, id::UserId},
     prelude::Context,
 };
 use sqlx::PgPool;
 use tokio::time::{interval, Interval};
 
 use crate::{
-    entities::{Guild, InvitePollOutcome, InvitePollWithVoteCount},
+    entities::{
+        required_votes, resolve_delegated_votes, vote_weight, AuditEvent, AuditEventKind,
+        ChoicePollOutcome, ChoicePollWithVoteCount, ChoiceResult, CommunityPollOutcome,
+        CommunityPollWithVoteCount, Decision, Delegation, DueInvitePollReminder, Guild,
+        GuildVoteWeight, InvitePoll, InvitePollId, InvitePollMessageRefresh, InvitePollNudge,
+        InvitePollOutcome, InvitePollReminder, InvitePollVoteEvent, InvitePollVoteSubmission,
+        InvitePollWithVoteCount, KickPollOutcome, KickPollWithVoteCount, NotificationPreference,
+        PollMessage, PollRef, RejectionReason, MAX_FIELD_LENGTH,
+    },
     error::Error,
-    util::serenity::ErrorExt,
+    util::{
+        serenity::{ErrorExt, GuildExt},
+        DiscordTimestamp, DiscordTimestampStyle,
+    },
     POOL,
 };
 
 #[derive(Debug, thiserror::Error)]
 enum InvitePollMessage {
-    #[error("{0} users opposed")]
-    AtLeastOneOpposition(i64),
-
-    #[error("the quorum was not reached: {0}/{1} users voted")]
-    QuorumNotReached(i64, i64),
+    #[error(transparent)]
+    Rejected(#[from] RejectionReason),
 
     #[error("{0}")]
     InviteUrl(String),
 }
 
+#[derive(Debug, thiserror::Error)]
+enum KickPollMessage {
+    #[error(transparent)]
+    Rejected(#[from] RejectionReason),
+
+    #[error("the member already left")]
+    AlreadyLeft,
+
+    #[error("the member could not be kicked, check the permissions and the role of the bot")]
+    KickFailed,
+}
+
+const TIE_MESSAGE_PREFIX: &'static str = "tied between ";
+
+#[derive(Debug, thiserror::Error)]
+enum ChoicePollMessage {
+    #[error("tied between {0}")]
+    Tie(String),
+}
+
+/// How long before the end of an invite poll the members who opted in are nudged to vote.
+const NUDGE_BEFORE: Duration = Duration::from_secs(12 * 60 * 60); // 12 hours
+
+/// Sends a direct message to `user_id`, returns `false` if they do not accept direct messages.
+async fn send_direct_message(
+    http: &Http,
+    user_id: impl Into<UserId>,
+    content: String,
+) -> Result<bool, Error> {
+    let pm = user_id.into().create_dm_channel(http).await?;
+    let res = pm
+        .send_message(http, CreateMessage::default().content(content))
+        .await;
+
+    match res {
+        Ok(_) => Ok(true),
+        Err(err) if err.is_cannot_send_messages_to_this_user_error() => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
 pub struct BackgroundPollHandler {
     ctx: Context,
     interval: Interval,
@@ -55,26 +105,316 @@ impl BackgroundPollHandler {
     async fn tick(&self, pool: &PgPool) -> Result<(), Error> {
         let polls = InvitePollWithVoteCount::find_expired(pool).await?;
         for mut poll in polls {
-            match self.close_poll(pool, &mut poll).await {
+            match Self::close_invite_poll(&self.ctx, pool, &mut poll, None).await {
                 Ok(()) => {}
                 Err(err) => error!(
                     "failed to tick expired poll {}: {:?}",
                     poll.invite_poll.id, err
                 ),
             }
-            {}
+        }
+
+        let reminders = InvitePollReminder::find_due(pool).await?;
+        for reminder in reminders {
+            match self.send_invite_poll_reminder(pool, &reminder).await {
+                Ok(()) => {}
+                Err(err) => error!(
+                    "failed to send reminder of poll {}: {:?}",
+                    reminder.invite_poll.id, err
+                ),
+            }
+        }
+
+        let polls = InvitePollNudge::find_due(pool, &NUDGE_BEFORE).await?;
+        for poll in polls {
+            match self.nudge_invite_poll_voters(pool, &poll).await {
+                Ok(()) => {}
+                Err(err) => error!("failed to nudge the voters of poll {}: {:?}", poll.id, err),
+            }
+        }
+
+        let refreshes = InvitePollMessageRefresh::find_all(pool).await?;
+        for refresh in refreshes {
+            match self
+                .refresh_invite_poll_message(pool, &refresh.invite_poll_id)
+                .await
+            {
+                Ok(()) => {}
+                Err(err) => error!(
+                    "failed to refresh the message of poll {}: {:?}",
+                    refresh.invite_poll_id, err
+                ),
+            }
+        }
+
+        let polls = KickPollWithVoteCount::find_expired(pool).await?;
+        for mut poll in polls {
+            match self.close_kick_poll(pool, &mut poll).await {
+                Ok(()) => {}
+                Err(err) => error!(
+                    "failed to tick expired kick poll {}: {:?}",
+                    poll.kick_poll.id, err
+                ),
+            }
+        }
+
+        let polls = CommunityPollWithVoteCount::find_expired(pool).await?;
+        for mut poll in polls {
+            match self.close_community_poll(pool, &mut poll).await {
+                Ok(()) => {}
+                Err(err) => error!(
+                    "failed to tick expired community poll {}: {:?}",
+                    poll.community_poll.id, err
+                ),
+            }
+        }
+
+        let polls = ChoicePollWithVoteCount::find_expired(pool).await?;
+        for mut poll in polls {
+            match self.close_choice_poll(pool, &mut poll).await {
+                Ok(()) => {}
+                Err(err) => error!(
+                    "failed to tick expired choice poll {}: {:?}",
+                    poll.choice_poll.id, err
+                ),
+            }
         }
 
         Ok(())
     }
 
-    async fn close_poll(
+    /// Posts a reminder as a reply to the poll message, it is recorded in the same transaction so
+    /// that it is posted again on the next tick if posting fails.
+    async fn send_invite_poll_reminder(
         &self,
         pool: &PgPool,
-        poll: &mut InvitePollWithVoteCount,
+        reminder: &DueInvitePollReminder,
+    ) -> Result<(), Error> {
+        let poll = &reminder.invite_poll;
+        let (Some(channel_id), Some(message_id)) = (&poll.channel_id, &poll.message_id) else {
+            return Ok(());
+        };
+
+        let mut transaction = pool.begin().await?;
+        let created =
+            InvitePollReminder::create(&mut *transaction, &poll.id, &reminder.remind_before)
+                .await?;
+        if created.is_none() {
+            return Ok(());
+        }
+
+        let mut content = format!(
+            "The invite poll for {} ends {}, remember to vote!",
+            poll.invitee,
+            DiscordTimestamp::new(poll.ends_at, DiscordTimestampStyle::Relative)
+        );
+        let mut allowed_mentions = CreateAllowedMentions::new();
+        if let Some(role_id) = reminder.reminder_role_id.as_ref() {
+            content = format!("{} {}", role_id, content);
+            allowed_mentions = allowed_mentions.roles([role_id]);
+        }
+
+        channel_id
+            .send_message(
+                &self.ctx.http,
+                CreateMessage::default()
+                    .content(content)
+                    .allowed_mentions(allowed_mentions)
+                    .reference_message((**channel_id, **message_id)),
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    /// Sends a direct message to the eligible members who opted into vote nudges and have not voted
+    /// on `poll` yet.
+    async fn nudge_invite_poll_voters(
+        &self,
+        pool: &PgPool,
+        poll: &InvitePoll,
     ) -> Result<(), Error> {
         let http = &self.ctx.http;
 
+        // recorded first so that members are nudged at most once, even if some messages fail
+        if InvitePollNudge::create(pool, &poll.id).await?.is_none() {
+            return Ok(());
+        }
+
+        let subscribers =
+            NotificationPreference::find_vote_nudges_by_guild_id(pool, &poll.guild_id).await?;
+        if subscribers.is_empty() {
+            return Ok(());
+        }
+
+        let guild = poll.guild_id.to_partial_guild(http).await?;
+        let settings = Guild::find_by_id(pool, &poll.guild_id)
+            .await?
+            .ok_or_else(|| Error::GuildNotFound(poll.guild_id.clone()))?;
+        let voters = Self::invite_poll_voters(&self.ctx, pool, &settings, &guild).await?;
+        let votes = InvitePollVoteSubmission::find_by_invite_poll_id(pool, &poll.id).await?;
+
+        let content = format!(
+            "Hello! The invite poll for {} in **{}** ends {} and you have not voted yet!\n{}",
+            poll.invitee,
+            guild.name,
+            DiscordTimestamp::new(poll.ends_at, DiscordTimestampStyle::Relative),
+            poll.message_url().unwrap_or_default()
+        );
+
+        for subscriber in subscribers {
+            let eligible = voters.contains_key(&subscriber.user_id);
+            let voted = votes
+                .iter()
+                .any(|vote| *vote.user_id == *subscriber.user_id);
+            if !eligible || voted {
+                continue;
+            }
+
+            match send_direct_message(http, &subscriber.user_id, content.clone()).await {
+                Ok(true) => {}
+                Ok(false) => debug!(
+                    "could not nudge {} because they do not accept direct messages",
+                    subscriber.user_id
+                ),
+                Err(err) => error!("failed to nudge {}: {:?}", subscriber.user_id, err),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-renders the message of a poll whose state was changed outside of the bot.
+    async fn refresh_invite_poll_message(
+        &self,
+        pool: &PgPool,
+        invite_poll_id: &InvitePollId,
+    ) -> Result<(), Error> {
+        if let Some(mut poll) = InvitePollWithVoteCount::find_by_id(pool, invite_poll_id).await? {
+            poll.load_votes(pool).await?;
+
+            match Self::update_poll_message(&self.ctx, &poll).await {
+                Ok(()) => {}
+                // nothing left to refresh if the message was deleted
+                Err(Error::SerenityError(err)) if err.is_not_found_error() => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        InvitePollMessageRefresh::delete(pool, invite_poll_id).await
+    }
+
+    /// Re-renders the message of `poll`, if it was sent.
+    pub async fn update_poll_message(ctx: &Context, poll: &impl PollMessage) -> Result<(), Error> {
+        match poll.message_location() {
+            (Some(channel_id), Some(message_id)) => {
+                let renderer = poll.render(ctx.clone()).await?;
+
+                channel_id
+                    .edit_message(
+                        &ctx.http,
+                        message_id,
+                        renderer.render_edit_message(EditMessage::default()),
+                    )
+                    .await?;
+            }
+            _ => error!(
+                "could not update poll {} because either the `channel_id` or `message_id` are missing",
+                poll.poll_id()
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// The vote weight of each member of `guild` eligible to vote on invite polls.
+    pub async fn invite_poll_voters(
+        ctx: &Context,
+        pool: &PgPool,
+        settings: &Guild,
+        guild: &PartialGuild,
+    ) -> Result<HashMap<UserId, i32>, Error> {
+        let members = guild.human_members(&ctx.http).await?;
+        let weights = GuildVoteWeight::find_by_guild_id(pool, &settings.id).await?;
+
+        let res = members
+            .iter()
+            .filter(|member| settings.is_eligible_voter(member))
+            .map(|member| (member.user.id, vote_weight(&weights, member)))
+            .collect();
+
+        Ok(res)
+    }
+
+    /// Closes an open invite poll right away if the votes of the eligible members who have not
+    /// voted yet can no longer change its outcome, and the guild allows closing polls early.
+    pub async fn close_invite_poll_if_decided(
+        ctx: &Context,
+        pool: &PgPool,
+        invite_poll_id: &InvitePollId,
+    ) -> Result<(), Error> {
+        let mut poll = InvitePollWithVoteCount::find_by_id(pool, invite_poll_id)
+            .await?
+            .ok_or_else(|| Error::InvitePollNotFound(invite_poll_id.clone()))?;
+        if poll.invite_poll.outcome.is_some() {
+            return Ok(());
+        }
+
+        let settings = Guild::find_by_id(pool, &poll.invite_poll.guild_id)
+            .await?
+            .ok_or_else(|| Error::GuildNotFound(poll.invite_poll.guild_id.clone()))?;
+        if !settings.invite_poll_early_close {
+            return Ok(());
+        }
+
+        let guild = poll
+            .invite_poll
+            .guild_id
+            .to_partial_guild(&ctx.http)
+            .await?;
+        let voters = Self::invite_poll_voters(ctx, pool, &settings, &guild).await?;
+        let votes = InvitePollVoteSubmission::find_by_invite_poll_id(pool, invite_poll_id).await?;
+
+        let total: i32 = voters.values().sum();
+        let remaining: i32 = voters
+            .iter()
+            .filter(|(user_id, _)| !votes.iter().any(|vote| *vote.user_id == **user_id))
+            .map(|(_, weight)| weight)
+            .sum();
+
+        let quorum = required_votes(total as usize, settings.invite_poll_quorum);
+        let decided = settings.invite_poll_decision_rule.is_decided(
+            &poll.tally(),
+            quorum,
+            settings.abstentions_count_toward_quorum,
+            remaining.into(),
+        );
+        if !decided {
+            return Ok(());
+        }
+
+        match Self::close_invite_poll(ctx, pool, &mut poll, None).await {
+            // another vote closed the poll in the meantime
+            Err(Error::InvitePollClosed(_)) => Ok(()),
+            res => res,
+        }
+    }
+
+    /// Closes an invite poll using the guild's decision rule, sends the invite if the poll passed
+    /// and updates the poll message.
+    ///
+    /// Fails with `InvitePollClosed` if the poll was closed in the meantime. `closed_by` is the
+    /// manager forcing the close, if any.
+    pub async fn close_invite_poll(
+        ctx: &Context,
+        pool: &PgPool,
+        poll: &mut InvitePollWithVoteCount,
+        closed_by: Option<&crate::util::serenity::UserId>,
+    ) -> Result<(), Error> {
+        let http = &ctx.http;
+
         debug!("closing poll {:?}", poll);
 
         let guild = poll.invite_poll.guild_id.to_partial_guild(http).await?;
@@ -82,39 +422,75 @@ impl BackgroundPollHandler {
             .await?
             .ok_or_else(|| Error::GuildNotFound(poll.invite_poll.guild_id.clone()))?;
 
-        let guild_user_count = {
-            let mut max = 0_usize;
-            let mut after: Option<UserId> = None;
-            loop {
-                let page = guild.members(http, None, after).await?;
-                if page.len() == 0 {
-                    break;
+        // the quorum is relative to the total weight of the eligible voters
+        let voters = Self::invite_poll_voters(ctx, pool, &settings, &guild).await?;
+
+        // claim the poll so that it is closed and its invite created only once, the votes
+        // submitted in the meantime wait for the poll to be closed and are then rejected
+        let mut transaction = pool.begin().await?;
+        InvitePoll::find_open_for_update(&mut *transaction, &poll.invite_poll.id)
+            .await?
+            .ok_or_else(|| Error::InvitePollClosed(poll.invite_poll.id.clone()))?;
+
+        // the eligible members who did not vote inherit the vote of their delegate
+        {
+            let votes: HashMap<_, _> = InvitePollVoteSubmission::find_by_invite_poll_id(
+                &mut *transaction,
+                &poll.invite_poll.id,
+            )
+            .await?
+            .into_iter()
+            .map(|submission| (*submission.user_id, submission.vote))
+            .collect();
+            let delegations: HashMap<_, _> =
+                Delegation::find_by_guild_id(pool, &poll.invite_poll.guild_id)
+                    .await?
+                    .into_iter()
+                    .map(|delegation| (*delegation.delegator, *delegation.delegate))
+                    .collect();
+
+            let non_voters = voters
+                .keys()
+                .copied()
+                .filter(|user_id| !votes.contains_key(user_id));
+            let inherited = resolve_delegated_votes(non_voters, &votes, &delegations);
+
+            if !inherited.is_empty() {
+                for (delegator, delegate, vote) in inherited {
+                    let submission = InvitePollVoteSubmission::inherit(
+                        &mut *transaction,
+                        &poll.invite_poll.id,
+                        &delegator.into(),
+                        vote,
+                        voters[&delegator],
+                        &delegate.into(),
+                    )
+                    .await?;
+                    if let Some(submission) = submission {
+                        InvitePollVoteEvent::create(&mut *transaction, &submission, None).await?;
+                    }
                 }
 
-                max += page.iter().filter(|m| m.user.bot == false).count();
-                after = page.last().map(|u| u.user.id);
+                *poll =
+                    InvitePollWithVoteCount::find_by_id(&mut *transaction, &poll.invite_poll.id)
+                        .await?
+                        .ok_or_else(|| Error::InvitePollNotFound(poll.invite_poll.id.clone()))?;
             }
+        }
 
-            max
-        };
-
-        let (outcome, mut message) = {
-            let quorum = (guild_user_count as f32 * settings.invite_poll_quorum).ceil() as i64;
-            let count = poll.no_count + poll.yes_count;
-
-            if poll.no_count > 0 {
-                (
-                    InvitePollOutcome::Deny,
-                    Some(InvitePollMessage::AtLeastOneOpposition(poll.no_count)),
-                )
-            } else if count < quorum {
-                (
-                    InvitePollOutcome::Deny,
-                    Some(InvitePollMessage::QuorumNotReached(count, quorum)),
-                )
-            } else {
-                (InvitePollOutcome::Allow, None)
-            }
+        let total_weight: i32 = voters.values().sum();
+        let quorum = required_votes(total_weight as usize, settings.invite_poll_quorum);
+
+        let (outcome, mut message) = match settings.invite_poll_decision_rule.evaluate(
+            &poll.tally(),
+            quorum,
+            settings.abstentions_count_toward_quorum,
+        ) {
+            Decision::Approved => (InvitePollOutcome::Allow, None),
+            Decision::Rejected(reason) => (
+                InvitePollOutcome::Deny,
+                Some(InvitePollMessage::Rejected(reason)),
+            ),
         };
 
         debug!(
@@ -127,105 +503,359 @@ impl BackgroundPollHandler {
                 .unwrap_or("".to_string())
         );
 
+        // recorded once the outcome is persisted
+        let mut audit_events = Vec::new();
+
         if outcome == InvitePollOutcome::Allow {
             let invite = settings
                 .invite_channel_id
                 .create_invite(http, CreateInvite::default().unique(true).max_uses(1))
                 .await?;
 
-            'send_message: {
-                // try sending the server invite directly to the user
-                let pm = poll.invite_poll.invitee.create_dm_channel(http).await?;
-                let res = pm.send_message(http,
-                      CreateMessage::default() .content(format!( "Hello! You have been invited by {} to **{}**!\nAccept the following invite to join them!\n{}",
-                            poll.invite_poll.inviter,
-                            guild.name,
-                            invite.url()
-                            ))
-                )
-                .await;
+            // try sending the server invite directly to the user, if that fails send the invite to
+            // the inviter and then to the server owner
+            let owner: crate::util::serenity::UserId = guild.owner_id.into();
+            let recipients = [
+                (
+                    &poll.invite_poll.invitee,
+                    format!(
+                        "Hello! You have been invited by {} to **{}**!\nAccept the following invite to join them!\n{}",
+                        poll.invite_poll.inviter,
+                        guild.name,
+                        invite.url()
+                    ),
+                ),
+                (
+                    &poll.invite_poll.inviter,
+                    format!(
+                        "Hello! The invite poll you started in **{}** for {} has ended successfully!\nPlease send them the following invite url!\n{}",
+                        guild.name,
+                        poll.invite_poll.invitee,
+                        invite.url()
+                    ),
+                ),
+                (
+                    &owner,
+                    format!(
+                        "Hello! The invite poll in **{}** by {} for {} has ended successfully!\nPlease send them the following invite url!\n{}",
+                        guild.name,
+                        poll.invite_poll.inviter,
+                        poll.invite_poll.invitee,
+                        invite.url()
+                    ),
+                ),
+            ];
 
-                match res {
-                    Ok(_) => {
-                        break 'send_message;
-                    }
-                    Err(err) if err.is_cannot_send_messages_to_this_user_error() => {
-                        // continue
-                    }
-                    Err(err) => {
-                        return Err(err.into());
-                    }
+            let mut recipient = None;
+            for (user_id, content) in recipients {
+                if send_direct_message(http, user_id, content).await? {
+                    recipient = Some(user_id);
+                    break;
                 }
 
-                // if that fails send the invite to the inviter
-                let pm = poll.invite_poll.inviter.create_dm_channel(http).await?;
-                let res = pm.send_message(http,                     CreateMessage::default().content(format!(
-                            "Hello! The invite poll you started in **{}** for {} has ended successfully!\nPlease send them the following invite url!\n{}",
-                            guild.name,
-                            poll.invite_poll.invitee,
-                            invite.url()
-                            ))
-                                         )
+                audit_events.push((
+                    AuditEventKind::DeliveryFailed,
+                    format!("could not send the invite to {}", user_id),
+                ));
+            }
+
+            audit_events.push((
+                AuditEventKind::InviteGenerated,
+                match recipient {
+                    Some(user_id) => format!(
+                        "invite for {} sent to {}",
+                        poll.invite_poll.invitee, user_id
+                    ),
+                    None => format!(
+                        "invite for {} added to the poll message",
+                        poll.invite_poll.invitee
+                    ),
+                },
+            ));
+
+            // if everything fails fall back to putting it in the embed
+            if recipient.is_none() {
+                message = Some(InvitePollMessage::InviteUrl(invite.url()));
+            }
+        }
+
+        poll.invite_poll
+            .close(&mut *transaction, outcome, message.map(|r| r.to_string()))
+            .await?;
+        let forced_close = match closed_by {
+            Some(user_id) => Some(
+                AuditEvent::create(
+                    &mut *transaction,
+                    &poll.invite_poll.guild_id,
+                    AuditEventKind::PollForceClosed,
+                    Some(user_id),
+                    Some(&PollRef::Invite(poll.invite_poll.id.clone())),
+                    &format!("invite poll for {}", poll.invite_poll.invitee),
+                )
+                .await?,
+            ),
+            None => None,
+        };
+        transaction.commit().await?;
+
+        if let Some(event) = forced_close {
+            event.mirror(http, pool).await;
+        }
+
+        for (kind, details) in audit_events {
+            AuditEvent::record(
+                http,
+                pool,
+                &poll.invite_poll.guild_id,
+                kind,
+                None,
+                Some(&PollRef::Invite(poll.invite_poll.id.clone())),
+                &details,
+            )
+            .await;
+        }
+
+        poll.load_votes(pool).await?;
+
+        Self::update_poll_message(ctx, poll).await?;
+
+        // announce the result
+        if let Some(results_channel_id) = settings.results_channel_id.as_ref() {
+            let participation = poll
+                .tally()
+                .participation(settings.abstentions_count_toward_quorum);
+
+            // a failed announcement must not keep the reasons from reaching the inviter
+            if let Err(err) = results_channel_id
+                .send_message(
+                    http,
+                    CreateMessage::default().embed(poll.create_result_embed(participation, quorum)),
+                )
+                .await
+            {
+                error!(
+                    "failed to announce the result of poll {} in {}: {:?}",
+                    poll.invite_poll.id, results_channel_id, err
+                );
+                AuditEvent::record(
+                    http,
+                    pool,
+                    &poll.invite_poll.guild_id,
+                    AuditEventKind::DeliveryFailed,
+                    None,
+                    Some(&PollRef::Invite(poll.invite_poll.id.clone())),
+                    &format!("could not announce the result in {}", results_channel_id),
+                )
                 .await;
+            }
+        }
 
-                match res {
-                    Ok(_) => {
-                        break 'send_message;
-                    }
-                    Err(err) if err.is_cannot_send_messages_to_this_user_error() => {
-                        // continue
-                    }
-                    Err(err) => {
-                        return Err(err.into());
-                    }
-                }
+        // share the reasons of the no votes with the inviter
+        if !poll.reasons.is_empty() {
+            send_direct_message(
+                http,
+                &poll.invite_poll.inviter,
+                format!(
+                    "Hello! The invite poll you started in **{}** for {} has ended, the members who voted no gave the following reasons:\n{}",
+                    guild.name,
+                    poll.invite_poll.invitee,
+                    poll.render_reasons()
+                ),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn close_kick_poll(
+        &self,
+        pool: &PgPool,
+        poll: &mut KickPollWithVoteCount,
+    ) -> Result<(), Error> {
+        let http = &self.ctx.http;
+
+        debug!("closing kick poll {:?}", poll);
+
+        let guild = poll.kick_poll.guild_id.to_partial_guild(http).await?;
+        let settings = Guild::find_by_id(pool, &poll.kick_poll.guild_id)
+            .await?
+            .ok_or_else(|| Error::GuildNotFound(poll.kick_poll.guild_id.clone()))?;
+
+        // the target cannot vote on their own kick
+        let voters = guild
+            .human_members(http)
+            .await?
+            .into_iter()
+            .filter(|member| member.user.id != *poll.kick_poll.target)
+            .count();
+
+        let (outcome, mut message) = {
+            let quorum = required_votes(voters, settings.kick_poll_quorum);
+
+            match settings.kick_poll_decision_rule.evaluate(
+                &poll.tally(),
+                quorum,
+                settings.abstentions_count_toward_quorum,
+            ) {
+                Decision::Approved => (KickPollOutcome::Kick, None),
+                Decision::Rejected(reason) => (
+                    KickPollOutcome::Keep,
+                    Some(KickPollMessage::Rejected(reason)),
+                ),
+            }
+        };
+
+        debug!(
+            "closing kick poll {} with outcome {:?} and message {}",
+            poll.kick_poll.id,
+            outcome,
+            message
+                .as_ref()
+                .map(|r| r.to_string())
+                .unwrap_or("".to_string())
+        );
 
-                // if that fails send it to the server owner
-                let pm = guild.owner_id.create_dm_channel(http).await?;
-                let res = pm.send_message(http,                     CreateMessage::default().content(format!(
-                            "Hello! The invite poll in **{}** by {} for {} has ended successfully!\nPlease send them the following invite url!\n{}",
-                            guild.name,
-                            poll.invite_poll.inviter,
-                            poll.invite_poll.invitee,
-                            invite.url()
-                            ))
-                                         )
+        let mut audit_event = None;
+        if outcome == KickPollOutcome::Kick {
+            let reason = format!("kick poll {} passed", poll.kick_poll.id);
+            let res = guild
+                .kick_with_reason(http, &poll.kick_poll.target, &reason)
                 .await;
 
-                match res {
-                    Ok(_) => {
-                        break 'send_message;
-                    }
-                    Err(err) if err.is_cannot_send_messages_to_this_user_error() => {
-                        // continue
-                    }
-                    Err(err) => {
-                        return Err(err.into());
-                    }
+            match res {
+                Ok(()) => {
+                    audit_event = Some((
+                        AuditEventKind::MemberKicked,
+                        format!("{} was kicked", poll.kick_poll.target),
+                    ));
+                }
+                Err(err) if err.is_not_found_error() => {
+                    message = Some(KickPollMessage::AlreadyLeft);
+                }
+                // the poll is closed anyway so that the kick is not retried every tick
+                Err(err) => {
+                    error!(
+                        "failed to kick the target of kick poll {}: {:?}",
+                        poll.kick_poll.id, err
+                    );
+                    message = Some(KickPollMessage::KickFailed);
+                    audit_event = Some((
+                        AuditEventKind::KickFailed,
+                        format!("{} could not be kicked", poll.kick_poll.target),
+                    ));
                 }
-
-                // if everything fails fall back to putting it in the embed
-                message = Some(InvitePollMessage::InviteUrl(invite.url()));
             }
         }
 
-        poll.invite_poll
+        poll.kick_poll
             .close(pool, outcome, message.map(|r| r.to_string()))
             .await?;
 
-        match (&poll.invite_poll.channel_id, &poll.invite_poll.message_id) {
-            (Some(channel_id), Some(message_id)) => {
-                let renderer = poll.create_renderer(self.ctx.clone()).await?;
+        if let Some((kind, details)) = audit_event {
+            AuditEvent::record(
+                http,
+                pool,
+                &poll.kick_poll.guild_id,
+                kind,
+                None,
+                Some(&PollRef::Kick(poll.kick_poll.id.clone())),
+                &details,
+            )
+            .await;
+        }
 
-                channel_id
-                    .edit_message(http, message_id, renderer.render_edit_message(EditMessage::default()))
-                    .await?;
+        Self::update_poll_message(&self.ctx, poll).await?;
+
+        Ok(())
+    }
+
+    async fn close_community_poll(
+        &self,
+        pool: &PgPool,
+        poll: &mut CommunityPollWithVoteCount,
+    ) -> Result<(), Error> {
+        let http = &self.ctx.http;
+
+        debug!("closing community poll {:?}", poll);
+
+        let guild = poll.community_poll.guild_id.to_partial_guild(http).await?;
+        let settings = Guild::find_by_id(pool, &poll.community_poll.guild_id)
+            .await?
+            .ok_or_else(|| Error::GuildNotFound(poll.community_poll.guild_id.clone()))?;
+
+        let members = guild.human_members(http).await?;
+
+        let (outcome, message) = {
+            let quorum = required_votes(members.len(), poll.community_poll.quorum);
+
+            match poll.community_poll.decision_rule.evaluate(
+                &poll.tally(),
+                quorum,
+                settings.abstentions_count_toward_quorum,
+            ) {
+                Decision::Approved => (CommunityPollOutcome::Passed, None),
+                Decision::Rejected(reason) => (CommunityPollOutcome::Rejected, Some(reason)),
             }
-            _ => error!(
-                "could not update poll {} because either the `channel_id` or `message_id` are missing",
-                poll.invite_poll.id
-            ),
-        }
+        };
+
+        debug!(
+            "closing community poll {} with outcome {:?} and message {}",
+            poll.community_poll.id,
+            outcome,
+            message
+                .as_ref()
+                .map(|r| r.to_string())
+                .unwrap_or("".to_string())
+        );
+
+        poll.community_poll
+            .close(pool, outcome, message.map(|r| r.to_string()))
+            .await?;
+
+        Self::update_poll_message(&self.ctx, poll).await?;
+
+        Ok(())
+    }
+
+    async fn close_choice_poll(
+        &self,
+        pool: &PgPool,
+        poll: &mut ChoicePollWithVoteCount,
+    ) -> Result<(), Error> {
+        debug!("closing choice poll {:?}", poll);
+
+        let (outcome, winner, message) = match poll.result() {
+            ChoiceResult::Winner(winner) => (ChoicePollOutcome::Decided, Some(winner), None),
+            ChoiceResult::Tie(positions) => {
+                // the message is rendered in an embed field
+                let labels =
+                    poll.render_labels(&positions, MAX_FIELD_LENGTH - TIE_MESSAGE_PREFIX.len());
+
+                (
+                    ChoicePollOutcome::Tied,
+                    None,
+                    Some(ChoicePollMessage::Tie(labels)),
+                )
+            }
+            ChoiceResult::NoVotes => (ChoicePollOutcome::NoVotes, None, None),
+        };
+
+        debug!(
+            "closing choice poll {} with outcome {:?} and message {}",
+            poll.choice_poll.id,
+            outcome,
+            message
+                .as_ref()
+                .map(|r| r.to_string())
+                .unwrap_or("".to_string())
+        );
+
+        poll.choice_poll
+            .close(pool, outcome, winner, message.map(|r| r.to_string()))
+            .await?;
+
+        Self::update_poll_message(&self.ctx, poll).await?;
 
         Ok(())
     }
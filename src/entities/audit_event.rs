@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use serenity::{
+    builder::{CreateEmbed, CreateMessage},
+    http::Http,
+};
+use sqlx::{Executor, PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::{
+    error::Error,
+    util::{
+        colors,
+        serenity::{GuildId, UserId},
+    },
+};
+
+use super::{AuditEventKind, Guild, PollKind, PollRef};
+
+/// An event of the audit log of a guild.
+#[derive(Debug, sqlx::FromRow)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub guild_id: GuildId,
+    pub kind: AuditEventKind,
+    /// The user who performed the action, if any.
+    pub user_id: Option<UserId>,
+    /// The kind of the poll the event refers to, if any, see [`AuditEvent::poll`].
+    pub poll_kind: Option<PollKind>,
+    pub poll_id: Option<Uuid>,
+    pub details: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEvent {
+    pub async fn create<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+        kind: AuditEventKind,
+        user_id: Option<&UserId>,
+        poll: Option<&PollRef>,
+        details: &str,
+    ) -> Result<Self, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO audit_event (guild_id, kind, user_id, poll_kind, poll_id, details)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING *;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(kind)
+        .bind(user_id)
+        .bind(poll.map(PollRef::kind))
+        .bind(poll.map(PollRef::uuid))
+        .bind(details)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Persists the event and mirrors it to the log channel of the guild, if there is one.
+    ///
+    /// Failures are only logged so that they never interrupt the action being audited, which has
+    /// already been performed.
+    pub async fn record(
+        http: &Http,
+        pool: &PgPool,
+        guild_id: &GuildId,
+        kind: AuditEventKind,
+        user_id: Option<&UserId>,
+        poll: Option<&PollRef>,
+        details: &str,
+    ) {
+        match Self::create(pool, guild_id, kind, user_id, poll, details).await {
+            Ok(event) => event.mirror(http, pool).await,
+            Err(err) => error!(
+                "failed to record audit event `{}` of guild {:?}: {:?}",
+                kind, guild_id, err
+            ),
+        }
+    }
+
+    /// Mirrors the event to the log channel of the guild, if there is one, failures are only
+    /// logged.
+    pub async fn mirror(&self, http: &Http, pool: &PgPool) {
+        let res = match Guild::find_by_id(pool, &self.guild_id).await {
+            Ok(guild) => match guild.and_then(|guild| guild.log_channel_id) {
+                Some(log_channel_id) => log_channel_id
+                    .send_message(http, CreateMessage::default().embed(self.create_embed()))
+                    .await
+                    .map(|_| ())
+                    .map_err(Error::from),
+                None => Ok(()),
+            },
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = res {
+            error!(
+                "failed to mirror audit event {} to the log channel: {:?}",
+                self.id, err
+            );
+        }
+    }
+
+    /// The poll the event refers to, if any.
+    pub fn poll(&self) -> Option<PollRef> {
+        match (self.poll_kind, self.poll_id) {
+            (Some(kind), Some(id)) => Some(PollRef::from_parts(kind, id)),
+            _ => None,
+        }
+    }
+
+    pub fn create_embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::default()
+            .color(match self.kind {
+                AuditEventKind::DeliveryFailed | AuditEventKind::KickFailed => colors::DISCORD_RED,
+                _ => colors::DISCORD_BLURPLE,
+            })
+            .title(self.kind.to_string())
+            .description(&self.details)
+            .timestamp(self.created_at);
+
+        if let Some(user_id) = self.user_id.as_ref() {
+            embed = embed.field("User", user_id.to_string(), true);
+        }
+        if let Some(poll) = self.poll() {
+            embed = embed.field(poll.kind().to_string(), format!("`{}`", poll), true);
+        }
+
+        embed
+    }
+}
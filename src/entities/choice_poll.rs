@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serenity::model::prelude::Message;
+use sqlx::{postgres::types::PgInterval, Executor, PgExecutor, Postgres};
+
+use crate::{
+    error::Error,
+    util::serenity::{ChannelId, GuildId, MessageId, UserId},
+};
+
+use super::{ChoicePollId, ChoicePollMode, ChoicePollOutcome};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ChoicePoll {
+    pub id: ChoicePollId,
+    pub guild_id: GuildId,
+    pub author: UserId,
+    pub question: String,
+    pub description: Option<String>,
+    pub channel_id: Option<ChannelId>,
+    pub message_id: Option<MessageId>,
+    pub outcome: Option<ChoicePollOutcome>,
+    /// The position of the winning option.
+    pub winner: Option<i16>,
+    pub message: Option<String>,
+    pub ends_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub mode: ChoicePollMode,
+}
+
+impl ChoicePoll {
+    pub async fn create<'e, E>(
+        executor: E,
+        guild_id: &GuildId,
+        author: &UserId,
+        question: &str,
+        description: Option<&str>,
+        mode: ChoicePollMode,
+        duration: &Duration,
+    ) -> Result<Self, Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        let duration = PgInterval::try_from(*duration).map_err(sqlx::Error::Decode)?;
+
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO choice_poll (guild_id, author, question, description, mode, ends_at)
+                VALUES ($1, $2, $3, $4, $5, now() + $6)
+                RETURNING *;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(author)
+        .bind(question)
+        .bind(description)
+        .bind(mode)
+        .bind(duration)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn find_by_id<'c, E>(executor: E, id: &ChoicePollId) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM choice_poll
+                WHERE id = $1;
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn find_expired<'c, E>(executor: E) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM choice_poll
+                WHERE outcome IS NULL AND ends_at <= now();
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn update_message<'e, E>(
+        &mut self,
+        executor: E,
+        message: &Message,
+    ) -> Result<(), Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE choice_poll
+                SET channel_id = $2, message_id = $3
+                WHERE id = $1
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(ChannelId::from(message.channel_id))
+        .bind(MessageId::from(message.id))
+        .fetch_one(executor)
+        .await?;
+
+        *self = res;
+        Ok(())
+    }
+
+    pub async fn close<'c, E>(
+        &mut self,
+        executor: E,
+        outcome: ChoicePollOutcome,
+        winner: Option<i16>,
+        message: Option<String>,
+    ) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE choice_poll
+                SET outcome = $2, winner = $3, message = $4
+                WHERE id = $1
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(outcome)
+        .bind(winner)
+        .bind(message)
+        .fetch_one(executor)
+        .await?;
+
+        *self = res;
+        Ok(())
+    }
+}
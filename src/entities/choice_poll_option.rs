@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+use crate::error::Error;
+
+use super::ChoicePollId;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ChoicePollOption {
+    pub choice_poll_id: ChoicePollId,
+    /// The 0-based position of the option within the poll.
+    pub position: i16,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ChoicePollOption {
+    pub async fn create<'c, E>(
+        executor: E,
+        choice_poll_id: &ChoicePollId,
+        position: i16,
+        label: &str,
+    ) -> Result<Self, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO choice_poll_option (choice_poll_id, position, label)
+                VALUES ($1, $2, $3)
+                RETURNING *;
+            "#,
+        )
+        .bind(choice_poll_id)
+        .bind(position)
+        .bind(label)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
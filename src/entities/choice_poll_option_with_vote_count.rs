@@ -0,0 +1,60 @@
+use sqlx::{Executor, Postgres};
+
+use crate::error::Error;
+
+use super::{ChoicePollId, ChoicePollOption};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ChoicePollOptionWithVoteCount {
+    #[sqlx(flatten)]
+    pub option: ChoicePollOption,
+
+    pub vote_count: i64,
+}
+
+impl ChoicePollOptionWithVoteCount {
+    pub async fn find_by_choice_poll_id<'c, E>(
+        executor: E,
+        choice_poll_id: &ChoicePollId,
+    ) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM choice_poll_option_with_vote_count
+                WHERE choice_poll_id = $1
+                ORDER BY position;
+            "#,
+        )
+        .bind(choice_poll_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Loads the options of several polls at once, ordered by poll and position.
+    pub async fn find_by_choice_poll_ids<'c, E>(
+        executor: E,
+        choice_poll_ids: &[ChoicePollId],
+    ) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM choice_poll_option_with_vote_count
+                WHERE choice_poll_id = ANY($1)
+                ORDER BY choice_poll_id, position;
+            "#,
+        )
+        .bind(choice_poll_ids)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, PgConnection, Postgres};
+
+use crate::{error::Error, util::serenity::UserId};
+
+use super::ChoicePollId;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ChoicePollRanking {
+    pub choice_poll_id: ChoicePollId,
+    pub user_id: UserId,
+    /// The preference of the option, 0 being the most preferred.
+    pub rank: i16,
+    /// The position of the ranked option.
+    pub position: i16,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ChoicePollRanking {
+    /// Replaces the ranking of `user_id` with `positions`, ordered by preference.
+    pub async fn replace(
+        conn: &mut PgConnection,
+        choice_poll_id: &ChoicePollId,
+        user_id: &UserId,
+        positions: &[i16],
+    ) -> Result<Vec<Self>, Error> {
+        sqlx::query(
+            r#"
+                DELETE FROM choice_poll_ranking
+                WHERE choice_poll_id = $1 AND user_id = $2;
+            "#,
+        )
+        .bind(choice_poll_id)
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+
+        let mut res = Vec::with_capacity(positions.len());
+        for (rank, position) in positions.iter().enumerate() {
+            let ranking = sqlx::query_as::<_, Self>(
+                r#"
+                    INSERT INTO choice_poll_ranking (choice_poll_id, user_id, rank, position)
+                    VALUES ($1, $2, $3, $4)
+                    RETURNING *;
+                "#,
+            )
+            .bind(choice_poll_id)
+            .bind(user_id)
+            .bind(rank as i16)
+            .bind(position)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            res.push(ranking);
+        }
+
+        Ok(res)
+    }
+
+    /// Loads the ballots of a poll, each listing option positions ordered by preference.
+    pub async fn find_ballots_by_choice_poll_id<'c, E>(
+        executor: E,
+        choice_poll_id: &ChoicePollId,
+    ) -> Result<Vec<Vec<i16>>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let rows = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM choice_poll_ranking
+                WHERE choice_poll_id = $1
+                ORDER BY user_id, rank;
+            "#,
+        )
+        .bind(choice_poll_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(group_ballots(rows))
+    }
+
+    /// Loads the ballots of several polls at once, grouped by poll.
+    pub async fn find_ballots_by_choice_poll_ids<'c, E>(
+        executor: E,
+        choice_poll_ids: &[ChoicePollId],
+    ) -> Result<HashMap<ChoicePollId, Vec<Vec<i16>>>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let rows = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM choice_poll_ranking
+                WHERE choice_poll_id = ANY($1)
+                ORDER BY choice_poll_id, user_id, rank;
+            "#,
+        )
+        .bind(choice_poll_ids)
+        .fetch_all(executor)
+        .await?;
+
+        let mut rows_by_poll: HashMap<ChoicePollId, Vec<Self>> = HashMap::new();
+        for row in rows {
+            rows_by_poll
+                .entry(row.choice_poll_id.clone())
+                .or_default()
+                .push(row);
+        }
+
+        Ok(rows_by_poll
+            .into_iter()
+            .map(|(choice_poll_id, rows)| (choice_poll_id, group_ballots(rows)))
+            .collect())
+    }
+}
+
+/// Groups the rankings of a poll, ordered by user and rank, into one ballot per user.
+fn group_ballots(rows: Vec<ChoicePollRanking>) -> Vec<Vec<i16>> {
+    let mut res: Vec<Vec<i16>> = Vec::new();
+    let mut last_user_id: Option<UserId> = None;
+    for row in rows {
+        if last_user_id.as_deref() != Some(&*row.user_id) {
+            res.push(Vec::new());
+            last_user_id = Some(row.user_id.clone());
+        }
+
+        if let Some(ballot) = res.last_mut() {
+            ballot.push(row.position);
+        }
+    }
+
+    res
+}
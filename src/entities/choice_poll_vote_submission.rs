@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+use crate::{error::Error, util::serenity::UserId};
+
+use super::ChoicePollId;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ChoicePollVoteSubmission {
+    pub choice_poll_id: ChoicePollId,
+    pub user_id: UserId,
+    /// The position of the chosen option.
+    pub position: i16,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ChoicePollVoteSubmission {
+    pub async fn create_or_update<'c, E>(
+        executor: E,
+        choice_poll_id: &ChoicePollId,
+        user_id: &UserId,
+        position: i16,
+    ) -> Result<Self, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO choice_poll_vote_submission (choice_poll_id, user_id, position)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (choice_poll_id, user_id) DO UPDATE SET position = EXCLUDED.position
+                RETURNING *;
+            "#,
+        )
+        .bind(choice_poll_id)
+        .bind(user_id)
+        .bind(position)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
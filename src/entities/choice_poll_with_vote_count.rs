@@ -0,0 +1,357 @@
+use std::{collections::HashMap, fmt::Write};
+
+use async_trait::async_trait;
+use serenity::{
+    all::ButtonStyle,
+    builder::{
+        CreateActionRow, CreateButton, CreateEmbed, CreateSelectMenu, CreateSelectMenuKind,
+        CreateSelectMenuOption,
+    },
+    prelude::Context,
+};
+use sqlx::PgPool;
+
+use crate::{
+    error::Error,
+    util::{
+        colors, emojis,
+        serenity::{ChannelId, MessageId, MessageRenderer},
+        ProgressBar,
+    },
+};
+
+use super::{
+    instant_runoff, plurality, render, ChoicePoll, ChoicePollId, ChoicePollMode,
+    ChoicePollOptionWithVoteCount, ChoicePollOutcome, ChoicePollRanking, ChoiceResult, PollMessage,
+    RunoffRound,
+};
+
+/// Polls with up to this many options are rendered with buttons, otherwise with a select menu.
+const MAX_BUTTONS: usize = 5;
+
+/// The maximum length of the description of the poll and of its runoff rounds, so that both embeds
+/// stay within Discord's limit of 6000 characters per message.
+const MAX_DESCRIPTION_LENGTH: usize = 2048;
+
+/// The maximum length of the vote counts, or of the eliminated options, of a single runoff round.
+const MAX_ROUND_LENGTH: usize = 512;
+
+#[derive(Debug)]
+pub struct ChoicePollWithVoteCount {
+    pub choice_poll: ChoicePoll,
+    pub options: Vec<ChoicePollOptionWithVoteCount>,
+    /// The ballots of a ranked poll, each listing option positions ordered by preference.
+    pub ballots: Vec<Vec<i16>>,
+}
+
+impl ChoicePollWithVoteCount {
+    pub async fn find_by_id(pool: &PgPool, id: &ChoicePollId) -> Result<Option<Self>, Error> {
+        let choice_poll = match ChoicePoll::find_by_id(pool, id).await? {
+            Some(choice_poll) => choice_poll,
+            None => return Ok(None),
+        };
+
+        Ok(Some(Self::load(pool, choice_poll).await?))
+    }
+
+    pub async fn find_expired(pool: &PgPool) -> Result<Vec<Self>, Error> {
+        let choice_polls = ChoicePoll::find_expired(pool).await?;
+        if choice_polls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // the options and ballots of all the polls are loaded at once
+        let ids: Vec<_> = choice_polls.iter().map(|poll| poll.id.clone()).collect();
+        let mut options: HashMap<ChoicePollId, Vec<_>> = HashMap::new();
+        for option in ChoicePollOptionWithVoteCount::find_by_choice_poll_ids(pool, &ids).await? {
+            options
+                .entry(option.option.choice_poll_id.clone())
+                .or_default()
+                .push(option);
+        }
+        let mut ballots = ChoicePollRanking::find_ballots_by_choice_poll_ids(pool, &ids).await?;
+
+        let res = choice_polls
+            .into_iter()
+            .map(|choice_poll| Self {
+                options: options.remove(&choice_poll.id).unwrap_or_default(),
+                ballots: ballots.remove(&choice_poll.id).unwrap_or_default(),
+                choice_poll,
+            })
+            .collect();
+
+        Ok(res)
+    }
+
+    async fn load(pool: &PgPool, choice_poll: ChoicePoll) -> Result<Self, Error> {
+        let options =
+            ChoicePollOptionWithVoteCount::find_by_choice_poll_id(pool, &choice_poll.id).await?;
+
+        let ballots = match choice_poll.mode {
+            ChoicePollMode::Single => Vec::new(),
+            ChoicePollMode::Ranked => {
+                ChoicePollRanking::find_ballots_by_choice_poll_id(pool, &choice_poll.id).await?
+            }
+        };
+
+        Ok(Self {
+            choice_poll,
+            options,
+            ballots,
+        })
+    }
+
+    pub fn result(&self) -> ChoiceResult {
+        match self.choice_poll.mode {
+            ChoicePollMode::Single => {
+                let counts: Vec<_> = self
+                    .options
+                    .iter()
+                    .map(|opt| (opt.option.position, opt.vote_count))
+                    .collect();
+
+                plurality(&counts)
+            }
+            ChoicePollMode::Ranked => self.runoff().0,
+        }
+    }
+
+    fn runoff(&self) -> (ChoiceResult, Vec<RunoffRound>) {
+        let positions: Vec<_> = self.options.iter().map(|opt| opt.option.position).collect();
+        instant_runoff(&positions, &self.ballots)
+    }
+
+    fn render_rounds(&self) -> String {
+        let mut lines = Vec::new();
+
+        for (i, round) in self.runoff().1.iter().enumerate() {
+            let counts = round
+                .counts
+                .iter()
+                .map(|(position, count)| {
+                    format!("{} ({})", self.label(*position).unwrap_or_default(), count)
+                })
+                .collect::<Vec<_>>();
+            let mut line = format!(
+                "**Round {}:** {}",
+                i + 1,
+                render::truncated_join(&counts, ", ", MAX_ROUND_LENGTH)
+            );
+
+            if !round.eliminated.is_empty() {
+                let eliminated = round
+                    .eliminated
+                    .iter()
+                    .filter_map(|position| self.label(*position))
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>();
+
+                // writing to a `String` cannot fail
+                let _ = write!(
+                    line,
+                    " {} eliminated {}",
+                    emojis::CROSS_MARK,
+                    render::truncated_join(&eliminated, ", ", MAX_ROUND_LENGTH)
+                );
+            }
+
+            lines.push(line);
+        }
+
+        render::truncated_join(&lines, "\n", MAX_DESCRIPTION_LENGTH)
+    }
+
+    pub fn label(&self, position: i16) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|opt| opt.option.position == position)
+            .map(|opt| opt.option.label.as_str())
+    }
+
+    /// Renders the labels of the options at `positions`, truncated to fit in `max_length`.
+    pub fn render_labels(&self, positions: &[i16], max_length: usize) -> String {
+        let labels = positions
+            .iter()
+            .filter_map(|position| self.label(*position))
+            .map(|label| format!("`{}`", label))
+            .collect::<Vec<_>>();
+
+        render::truncated_join(&labels, ", ", max_length)
+    }
+
+    fn render_description(&self) -> String {
+        let mut blocks = Vec::new();
+
+        if let Some(description) = self.choice_poll.description.as_ref() {
+            blocks.push(format!("{}\n", description));
+        }
+
+        let mut bar = ProgressBar::builder();
+        bar.max(self.options.iter().map(|opt| opt.vote_count).sum())
+            .with_count(true)
+            .with_percentage(true);
+
+        for opt in &self.options {
+            let trophy = if self.choice_poll.winner == Some(opt.option.position) {
+                [emojis::TROPHY, " "].concat()
+            } else {
+                String::new()
+            };
+
+            blocks.push(format!(
+                "{}**{}.** {}\n{}",
+                trophy,
+                opt.option.position + 1,
+                opt.option.label,
+                bar.value(opt.vote_count).build().unwrap()
+            ));
+        }
+
+        render::truncated_join(&blocks, "\n", MAX_DESCRIPTION_LENGTH)
+    }
+
+    pub async fn create_renderer(&self, ctx: Context) -> Result<MessageRenderer, Error> {
+        let user = self.choice_poll.author.to_user(&ctx.http).await?;
+
+        let embeds = vec![{
+            let mut embed = CreateEmbed::default();
+
+            embed = embed
+                .color(match self.choice_poll.outcome {
+                    Some(ChoicePollOutcome::Decided) => colors::DISCORD_GREEN,
+                    Some(ChoicePollOutcome::Tied | ChoicePollOutcome::NoVotes) => {
+                        colors::DISCORD_YELLOW
+                    }
+                    None => colors::DISCORD_BLURPLE,
+                })
+                .title(&self.choice_poll.question)
+                .description(self.render_description())
+                .thumbnail(user.face());
+
+            // row
+            embed = render::summary_row(
+                embed,
+                &self.choice_poll.id,
+                ("Author", &user.name),
+                self.choice_poll.outcome.is_some(),
+            );
+
+            // row
+            embed = render::schedule_fields(
+                embed,
+                self.choice_poll.created_at,
+                self.choice_poll.ends_at,
+            )
+            .field(
+                "Voting",
+                match self.choice_poll.mode {
+                    ChoicePollMode::Single => "Single Choice",
+                    ChoicePollMode::Ranked => "Ranked Choice",
+                },
+                true,
+            );
+
+            // row
+            render::outcome_row(
+                embed,
+                self.choice_poll
+                    .outcome
+                    .map(|outcome| match (outcome, self.choice_poll.winner) {
+                        (ChoicePollOutcome::Decided, Some(winner)) => {
+                            [emojis::TROPHY, " ", self.label(winner).unwrap_or_default()].concat()
+                        }
+                        (ChoicePollOutcome::Decided, None) => emojis::TROPHY.to_owned(),
+                        (ChoicePollOutcome::Tied, _) => [emojis::WARNING, " Tied"].concat(),
+                        (ChoicePollOutcome::NoVotes, _) => [emojis::WARNING, " No Votes"].concat(),
+                    }),
+                self.choice_poll
+                    .message
+                    .as_deref()
+                    .map(|message| ("Reason", message)),
+            )
+        }];
+
+        // show the instant-runoff rounds so the result can be verified
+        let embeds = match (self.choice_poll.mode, self.choice_poll.outcome) {
+            (ChoicePollMode::Ranked, Some(_)) => {
+                let mut embeds = embeds;
+                embeds.push(
+                    CreateEmbed::default()
+                        .color(colors::DISCORD_BLURPLE)
+                        .title("Instant-Runoff Rounds")
+                        .description(self.render_rounds()),
+                );
+                embeds
+            }
+            _ => embeds,
+        };
+
+        let components = match self.choice_poll.outcome {
+            Some(_) => Vec::new(),
+            None if self.choice_poll.mode == ChoicePollMode::Ranked => {
+                vec![CreateActionRow::Buttons(vec![CreateButton::new(
+                    "democracy.choice-poll-rank",
+                )
+                .label("Rank Choices")
+                .style(ButtonStyle::Primary)])]
+            }
+            None if self.options.len() <= MAX_BUTTONS => {
+                vec![CreateActionRow::Buttons(
+                    self.options
+                        .iter()
+                        .map(|opt| {
+                            CreateButton::new(format!(
+                                "democracy.choice-poll-vote.{}",
+                                opt.option.position
+                            ))
+                            .label(&opt.option.label)
+                            .style(ButtonStyle::Primary)
+                        })
+                        .collect(),
+                )]
+            }
+            None => vec![CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    "democracy.choice-poll-vote",
+                    CreateSelectMenuKind::String {
+                        options: self
+                            .options
+                            .iter()
+                            .map(|opt| {
+                                CreateSelectMenuOption::new(
+                                    &opt.option.label,
+                                    opt.option.position.to_string(),
+                                )
+                            })
+                            .collect(),
+                    },
+                )
+                .placeholder("Choose an option"),
+            )],
+        };
+
+        let mut res = MessageRenderer::default();
+        res.set_components(components);
+        res.set_embeds(embeds);
+
+        Ok(res)
+    }
+}
+
+#[async_trait]
+impl PollMessage for ChoicePollWithVoteCount {
+    fn poll_id(&self) -> String {
+        self.choice_poll.id.to_string()
+    }
+
+    fn message_location(&self) -> (Option<&ChannelId>, Option<&MessageId>) {
+        (
+            self.choice_poll.channel_id.as_ref(),
+            self.choice_poll.message_id.as_ref(),
+        )
+    }
+
+    async fn render(&self, ctx: Context) -> Result<MessageRenderer, Error> {
+        self.create_renderer(ctx).await
+    }
+}
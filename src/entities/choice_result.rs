@@ -0,0 +1,169 @@
+/// The result of counting the votes of a poll with multiple options.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChoiceResult {
+    /// The position of the option with the most votes.
+    Winner(i16),
+    /// The positions of the options sharing the most votes.
+    Tie(Vec<i16>),
+    NoVotes,
+}
+
+/// Picks the option with the most votes given `(position, vote count)` pairs.
+pub fn plurality(counts: &[(i16, i64)]) -> ChoiceResult {
+    let max = counts.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    if max == 0 {
+        return ChoiceResult::NoVotes;
+    }
+
+    let leaders: Vec<i16> = counts
+        .iter()
+        .filter(|(_, count)| *count == max)
+        .map(|(position, _)| *position)
+        .collect();
+
+    match leaders.as_slice() {
+        [winner] => ChoiceResult::Winner(*winner),
+        _ => ChoiceResult::Tie(leaders),
+    }
+}
+
+/// A counting round of an instant-runoff vote.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunoffRound {
+    /// The `(position, vote count)` pairs of the options still running.
+    pub counts: Vec<(i16, i64)>,
+    /// The positions of the options eliminated at the end of the round.
+    pub eliminated: Vec<i16>,
+}
+
+/// Runs an instant-runoff vote between `options` given ballots ranking them by preference.
+///
+/// Each round counts every ballot toward its highest ranked option still running, an option with
+/// more than half of the counted ballots wins, otherwise the options with the fewest votes are
+/// eliminated.
+pub fn instant_runoff(options: &[i16], ballots: &[Vec<i16>]) -> (ChoiceResult, Vec<RunoffRound>) {
+    let mut running: Vec<i16> = options.to_vec();
+    let mut rounds = Vec::new();
+
+    loop {
+        let counts: Vec<(i16, i64)> = running
+            .iter()
+            .map(|position| {
+                let count = ballots
+                    .iter()
+                    .filter(|ballot| {
+                        ballot.iter().find(|choice| running.contains(choice)) == Some(position)
+                    })
+                    .count();
+
+                (*position, count as i64)
+            })
+            .collect();
+
+        let total: i64 = counts.iter().map(|(_, count)| count).sum();
+        if total == 0 {
+            rounds.push(RunoffRound {
+                counts,
+                eliminated: Vec::new(),
+            });
+            return (ChoiceResult::NoVotes, rounds);
+        }
+
+        if let Some((winner, _)) = counts.iter().find(|(_, count)| count * 2 > total) {
+            let winner = *winner;
+            rounds.push(RunoffRound {
+                counts,
+                eliminated: Vec::new(),
+            });
+            return (ChoiceResult::Winner(winner), rounds);
+        }
+
+        let min = counts.iter().map(|(_, count)| *count).min().unwrap_or(0);
+        let eliminated: Vec<i16> = counts
+            .iter()
+            .filter(|(_, count)| *count == min)
+            .map(|(position, _)| *position)
+            .collect();
+
+        // every remaining option is tied
+        if eliminated.len() == running.len() {
+            rounds.push(RunoffRound {
+                counts,
+                eliminated: Vec::new(),
+            });
+            return (ChoiceResult::Tie(running), rounds);
+        }
+
+        running.retain(|position| !eliminated.contains(position));
+        rounds.push(RunoffRound { counts, eliminated });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plurality() {
+        assert_eq!(
+            plurality(&[(0, 1), (1, 3), (2, 2)]),
+            ChoiceResult::Winner(1)
+        );
+        assert_eq!(
+            plurality(&[(0, 2), (1, 1), (2, 2)]),
+            ChoiceResult::Tie(vec![0, 2])
+        );
+        assert_eq!(plurality(&[(0, 0), (1, 0)]), ChoiceResult::NoVotes);
+        assert_eq!(plurality(&[]), ChoiceResult::NoVotes);
+    }
+
+    #[test]
+    fn test_instant_runoff_majority_in_first_round() {
+        let ballots = vec![vec![0, 1], vec![0, 2], vec![1, 0]];
+        let (result, rounds) = instant_runoff(&[0, 1, 2], &ballots);
+        assert_eq!(result, ChoiceResult::Winner(0));
+        assert_eq!(
+            rounds,
+            vec![RunoffRound {
+                counts: vec![(0, 2), (1, 1), (2, 0)],
+                eliminated: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_instant_runoff_transfers_votes() {
+        let ballots = vec![vec![0], vec![0], vec![1], vec![1], vec![2, 1]];
+        let (result, rounds) = instant_runoff(&[0, 1, 2], &ballots);
+        assert_eq!(result, ChoiceResult::Winner(1));
+        assert_eq!(
+            rounds,
+            vec![
+                RunoffRound {
+                    counts: vec![(0, 2), (1, 2), (2, 1)],
+                    eliminated: vec![2],
+                },
+                RunoffRound {
+                    counts: vec![(0, 2), (1, 3)],
+                    eliminated: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instant_runoff_exhausted_ballots() {
+        // the ballot ranking only the eliminated option no longer counts
+        let ballots = vec![vec![0], vec![1], vec![1], vec![0], vec![2]];
+        let (result, rounds) = instant_runoff(&[0, 1, 2], &ballots);
+        assert_eq!(result, ChoiceResult::Tie(vec![0, 1]));
+        assert_eq!(rounds.len(), 2);
+    }
+
+    #[test]
+    fn test_instant_runoff_no_votes() {
+        let (result, rounds) = instant_runoff(&[0, 1], &[]);
+        assert_eq!(result, ChoiceResult::NoVotes);
+        assert_eq!(rounds.len(), 1);
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serenity::model::prelude::Message;
+use sqlx::{postgres::types::PgInterval, Executor, PgExecutor, Postgres};
+
+use crate::{
+    error::Error,
+    util::serenity::{ChannelId, GuildId, MessageId, UserId},
+};
+
+use super::{CommunityPollId, CommunityPollOutcome, DecisionRule};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct CommunityPoll {
+    pub id: CommunityPollId,
+    pub guild_id: GuildId,
+    pub author: UserId,
+    pub question: String,
+    pub description: Option<String>,
+    /// The minimum number of votes required to consider the poll valid (0.0 - 1.0).
+    pub quorum: f32,
+    pub decision_rule: DecisionRule,
+    pub channel_id: Option<ChannelId>,
+    pub message_id: Option<MessageId>,
+    pub outcome: Option<CommunityPollOutcome>,
+    pub message: Option<String>,
+    pub ends_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The settings of a community poll about to be created.
+#[derive(Debug)]
+pub struct NewCommunityPoll<'a> {
+    pub guild_id: &'a GuildId,
+    pub author: &'a UserId,
+    pub question: &'a str,
+    pub description: Option<&'a str>,
+    pub quorum: f32,
+    pub decision_rule: DecisionRule,
+    pub duration: &'a Duration,
+}
+
+impl CommunityPoll {
+    pub async fn create<'e, E>(executor: E, poll: &NewCommunityPoll<'_>) -> Result<Self, Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        let duration = PgInterval::try_from(*poll.duration).map_err(sqlx::Error::Decode)?;
+
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO community_poll (
+                    guild_id, author, question, description, quorum, decision_rule, ends_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, now() + $7)
+                RETURNING *;
+            "#,
+        )
+        .bind(poll.guild_id)
+        .bind(poll.author)
+        .bind(poll.question)
+        .bind(poll.description)
+        .bind(poll.quorum)
+        .bind(poll.decision_rule)
+        .bind(duration)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn update_message<'e, E>(
+        &mut self,
+        executor: E,
+        message: &Message,
+    ) -> Result<(), Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE community_poll
+                SET channel_id = $2, message_id = $3
+                WHERE id = $1
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(ChannelId::from(message.channel_id))
+        .bind(MessageId::from(message.id))
+        .fetch_one(executor)
+        .await?;
+
+        *self = res;
+        Ok(())
+    }
+
+    pub async fn close<'c, E>(
+        &mut self,
+        executor: E,
+        outcome: CommunityPollOutcome,
+        message: Option<String>,
+    ) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE community_poll
+                SET outcome = $2, message = $3
+                WHERE id = $1
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(outcome)
+        .bind(message)
+        .fetch_one(executor)
+        .await?;
+
+        *self = res;
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use serenity::{builder::CreateEmbed, prelude::Context};
+use sqlx::{Executor, Postgres};
+
+use crate::{
+    error::Error,
+    util::{
+        colors, emojis,
+        serenity::{ChannelId, MessageId, MessageRenderer},
+    },
+};
+
+use super::{render, CommunityPoll, CommunityPollId, CommunityPollOutcome, PollMessage, VoteTally};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct CommunityPollWithVoteCount {
+    #[sqlx(flatten)]
+    pub community_poll: CommunityPoll,
+
+    pub yes_count: i64,
+    pub no_count: i64,
+    pub abstain_count: i64,
+}
+
+impl CommunityPollWithVoteCount {
+    pub async fn find_by_id<'c, E>(executor: E, id: &CommunityPollId) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM community_poll_with_vote_count
+                WHERE id = $1;
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn find_expired<'c, E>(executor: E) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM community_poll_with_vote_count
+                WHERE outcome IS NULL AND ends_at <= now();
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub fn tally(&self) -> VoteTally {
+        VoteTally {
+            yes: self.yes_count,
+            no: self.no_count,
+            abstain: self.abstain_count,
+        }
+    }
+
+    pub async fn create_renderer(&self, ctx: Context) -> Result<MessageRenderer, Error> {
+        let user = self.community_poll.author.to_user(&ctx.http).await?;
+
+        let embeds = vec![{
+            let mut embed = CreateEmbed::default();
+
+            embed = embed
+                .color(match self.community_poll.outcome {
+                    Some(CommunityPollOutcome::Passed) => colors::DISCORD_GREEN,
+                    Some(CommunityPollOutcome::Rejected) => colors::DISCORD_RED,
+                    None => colors::DISCORD_BLURPLE,
+                })
+                .title(&self.community_poll.question)
+                .thumbnail(user.face());
+
+            if let Some(description) = self.community_poll.description.as_ref() {
+                embed = embed.description(description);
+            }
+
+            // row
+            embed = render::summary_row(
+                embed,
+                &self.community_poll.id,
+                ("Author", &user.name),
+                self.community_poll.outcome.is_some(),
+            );
+
+            // row
+            embed = render::schedule_fields(
+                embed,
+                self.community_poll.created_at,
+                self.community_poll.ends_at,
+            );
+
+            // row
+            embed = embed.field("Votes", render::vote_bars(&self.tally()), false);
+
+            // row
+            render::outcome_row(
+                embed,
+                self.community_poll.outcome.map(|outcome| match outcome {
+                    CommunityPollOutcome::Passed => [emojis::CHECK_MARK_BUTTON, " Passed"].concat(),
+                    CommunityPollOutcome::Rejected => [emojis::NO_ENTRY, " Rejected"].concat(),
+                }),
+                self.community_poll.message.as_deref().map(|message| {
+                    let label = match self.community_poll.outcome {
+                        Some(CommunityPollOutcome::Rejected) => "Reason",
+                        Some(CommunityPollOutcome::Passed) | None => "",
+                    };
+                    (label, message)
+                }),
+            )
+        }];
+
+        let components = match self.community_poll.outcome {
+            Some(_) => Vec::new(),
+            None => vec![render::vote_buttons("democracy.community-poll-vote")],
+        };
+
+        let mut res = MessageRenderer::default();
+        res.set_components(components);
+        res.set_embeds(embeds);
+
+        Ok(res)
+    }
+}
+
+#[async_trait]
+impl PollMessage for CommunityPollWithVoteCount {
+    fn poll_id(&self) -> String {
+        self.community_poll.id.to_string()
+    }
+
+    fn message_location(&self) -> (Option<&ChannelId>, Option<&MessageId>) {
+        (
+            self.community_poll.channel_id.as_ref(),
+            self.community_poll.message_id.as_ref(),
+        )
+    }
+
+    async fn render(&self, ctx: Context) -> Result<MessageRenderer, Error> {
+        self.create_renderer(ctx).await
+    }
+}
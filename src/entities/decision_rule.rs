@@ -0,0 +1,365 @@
+use std::{fmt::Display, str::FromStr};
+
+use sqlx::Postgres;
+
+/// Decides whether a poll passes given the votes it received.
+///
+/// Every rule additionally requires the quorum to be reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecisionRule {
+    /// A single opposing vote rejects the poll.
+    Veto,
+    /// More votes in favour than opposed.
+    Majority,
+    /// At least the given percentage (0 - 100) of the cast votes are in favour.
+    Supermajority(u8),
+    /// At most the given number of opposing votes.
+    MaxOpposition(u32),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VoteTally {
+    pub yes: i64,
+    pub no: i64,
+    pub abstain: i64,
+}
+
+impl VoteTally {
+    /// The number of votes cast in favour or against.
+    pub fn total(&self) -> i64 {
+        self.yes + self.no
+    }
+
+    /// The number of votes counted toward the quorum.
+    pub fn participation(&self, count_abstentions: bool) -> i64 {
+        if count_abstentions {
+            self.total() + self.abstain
+        } else {
+            self.total()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Decision {
+    Approved,
+    Rejected(RejectionReason),
+}
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RejectionReason {
+    #[error("{0} users opposed")]
+    AtLeastOneOpposition(i64),
+
+    #[error("the quorum was not reached: {0}/{1} users voted")]
+    QuorumNotReached(i64, i64),
+
+    #[error("the majority was not reached: {yes} in favour, {no} opposed")]
+    MajorityNotReached { yes: i64, no: i64 },
+
+    #[error(
+        "the supermajority was not reached: {yes}/{total} votes in favour, {threshold}% required"
+    )]
+    SupermajorityNotReached { yes: i64, total: i64, threshold: u8 },
+
+    #[error("{count} users opposed, at most {max} allowed")]
+    TooManyOppositions { count: i64, max: u32 },
+}
+
+/// The number of votes required to reach a `quorum` (0.0 - 1.0) out of `voters` eligible voters.
+pub fn required_votes(voters: usize, quorum: f32) -> i64 {
+    (voters as f32 * quorum).ceil() as i64
+}
+
+impl DecisionRule {
+    /// Evaluates the `tally` of a poll requiring at least `quorum` votes, abstentions are only
+    /// counted toward the quorum if `count_abstentions` is set.
+    pub fn evaluate(&self, tally: &VoteTally, quorum: i64, count_abstentions: bool) -> Decision {
+        // a veto is reported even if the quorum was not reached
+        if let Self::Veto = self {
+            if tally.no > 0 {
+                return Decision::Rejected(RejectionReason::AtLeastOneOpposition(tally.no));
+            }
+        }
+
+        let participation = tally.participation(count_abstentions);
+        if participation < quorum {
+            return Decision::Rejected(RejectionReason::QuorumNotReached(participation, quorum));
+        }
+
+        match *self {
+            Self::Veto => Decision::Approved,
+            Self::Majority if tally.yes > tally.no => Decision::Approved,
+            Self::Majority => Decision::Rejected(RejectionReason::MajorityNotReached {
+                yes: tally.yes,
+                no: tally.no,
+            }),
+            Self::Supermajority(threshold)
+                if tally.total() > 0 && tally.yes * 100 >= tally.total() * threshold as i64 =>
+            {
+                Decision::Approved
+            }
+            Self::Supermajority(threshold) => {
+                Decision::Rejected(RejectionReason::SupermajorityNotReached {
+                    yes: tally.yes,
+                    total: tally.total(),
+                    threshold,
+                })
+            }
+            Self::MaxOpposition(max) if tally.no <= max as i64 => Decision::Approved,
+            Self::MaxOpposition(max) => Decision::Rejected(RejectionReason::TooManyOppositions {
+                count: tally.no,
+                max,
+            }),
+        }
+    }
+}
+
+impl DecisionRule {
+    /// Whether the decision on `tally` can no longer change, whatever the `remaining` votes that
+    /// could still be cast are.
+    ///
+    /// Additional votes in favour can only help a poll pass while opposing ones can only hinder
+    /// it, so it is enough to check the extremes.
+    pub fn is_decided(
+        &self,
+        tally: &VoteTally,
+        quorum: i64,
+        count_abstentions: bool,
+        remaining: i64,
+    ) -> bool {
+        let best = VoteTally {
+            yes: tally.yes + remaining,
+            ..*tally
+        };
+        if let Decision::Rejected(_) = self.evaluate(&best, quorum, count_abstentions) {
+            return true;
+        }
+
+        let worst = VoteTally {
+            no: tally.no + remaining,
+            ..*tally
+        };
+        self.evaluate(tally, quorum, count_abstentions) == Decision::Approved
+            && self.evaluate(&worst, quorum, count_abstentions) == Decision::Approved
+    }
+}
+
+impl Display for DecisionRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Veto => write!(f, "veto"),
+            Self::Majority => write!(f, "majority"),
+            Self::Supermajority(threshold) => write!(f, "supermajority:{}", threshold),
+            Self::MaxOpposition(max) => write!(f, "max-opposition:{}", max),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseDecisionRuleError {
+    #[error("unknown decision rule `{0}`, expected one of `veto`, `majority`, `supermajority:<percentage>` or `max-opposition:<count>`")]
+    UnknownRule(String),
+
+    #[error("decision rule `{0}` requires a parameter")]
+    MissingParameter(String),
+
+    #[error("decision rule `{0}` does not take a parameter")]
+    UnexpectedParameter(String),
+
+    #[error("invalid parameter `{0}`: {1}")]
+    InvalidParameter(String, #[source] std::num::ParseIntError),
+
+    #[error("percentage `{0}` must be between 0 and 100")]
+    PercentageOutOfRange(u8),
+}
+
+impl FromStr for DecisionRule {
+    type Err = ParseDecisionRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        let (name, param) = match s.split_once(':') {
+            Some((name, param)) => (name.trim(), Some(param.trim())),
+            None => (s.as_str(), None),
+        };
+
+        match (name, param) {
+            ("veto", None) => Ok(Self::Veto),
+            ("majority", None) => Ok(Self::Majority),
+            ("veto" | "majority", Some(_)) => {
+                Err(ParseDecisionRuleError::UnexpectedParameter(name.to_owned()))
+            }
+            ("supermajority", Some(param)) => {
+                let threshold = param.trim_end_matches('%').parse::<u8>().map_err(|err| {
+                    ParseDecisionRuleError::InvalidParameter(param.to_owned(), err)
+                })?;
+                if threshold > 100 {
+                    return Err(ParseDecisionRuleError::PercentageOutOfRange(threshold));
+                }
+                Ok(Self::Supermajority(threshold))
+            }
+            ("max-opposition", Some(param)) => {
+                let max = param.parse::<u32>().map_err(|err| {
+                    ParseDecisionRuleError::InvalidParameter(param.to_owned(), err)
+                })?;
+                Ok(Self::MaxOpposition(max))
+            }
+            ("supermajority" | "max-opposition", None) => {
+                Err(ParseDecisionRuleError::MissingParameter(name.to_owned()))
+            }
+            _ => Err(ParseDecisionRuleError::UnknownRule(s.clone())),
+        }
+    }
+}
+
+impl sqlx::Type<Postgres> for DecisionRule {
+    fn type_info() -> <Postgres as sqlx::Database>::TypeInfo {
+        <String as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &<Postgres as sqlx::Database>::TypeInfo) -> bool {
+        <String as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for DecisionRule {
+    fn decode(
+        value: <Postgres as sqlx::database::HasValueRef<'r>>::ValueRef,
+    ) -> Result<Self, sqlx::error::BoxDynError> {
+        let s = value.as_str()?;
+        Ok(s.parse()?)
+    }
+}
+
+impl<'q> sqlx::Encode<'q, Postgres> for DecisionRule {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <Postgres as sqlx::database::HasArguments<'q>>::ArgumentBuffer,
+    ) -> sqlx::encode::IsNull {
+        buf.extend(self.to_string().as_bytes());
+        sqlx::encode::IsNull::No
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(yes: i64, no: i64) -> VoteTally {
+        VoteTally {
+            yes,
+            no,
+            abstain: 0,
+        }
+    }
+
+    #[test]
+    fn test_veto() {
+        let rule = DecisionRule::Veto;
+        assert_eq!(rule.evaluate(&tally(5, 0), 5, false), Decision::Approved);
+        assert_eq!(
+            rule.evaluate(&tally(9, 1), 5, false),
+            Decision::Rejected(RejectionReason::AtLeastOneOpposition(1))
+        );
+        assert_eq!(
+            rule.evaluate(&tally(4, 0), 5, false),
+            Decision::Rejected(RejectionReason::QuorumNotReached(4, 5))
+        );
+    }
+
+    #[test]
+    fn test_majority() {
+        let rule = DecisionRule::Majority;
+        assert_eq!(rule.evaluate(&tally(3, 2), 5, false), Decision::Approved);
+        assert_eq!(
+            rule.evaluate(&tally(3, 3), 5, false),
+            Decision::Rejected(RejectionReason::MajorityNotReached { yes: 3, no: 3 })
+        );
+        assert_eq!(
+            rule.evaluate(&tally(3, 1), 5, false),
+            Decision::Rejected(RejectionReason::QuorumNotReached(4, 5))
+        );
+    }
+
+    #[test]
+    fn test_supermajority() {
+        let rule = DecisionRule::Supermajority(67);
+        assert_eq!(rule.evaluate(&tally(7, 3), 0, false), Decision::Approved);
+        assert_eq!(
+            rule.evaluate(&tally(6, 4), 0, false),
+            Decision::Rejected(RejectionReason::SupermajorityNotReached {
+                yes: 6,
+                total: 10,
+                threshold: 67
+            })
+        );
+        assert!(matches!(
+            rule.evaluate(&tally(0, 0), 0, false),
+            Decision::Rejected(RejectionReason::SupermajorityNotReached { .. })
+        ));
+    }
+
+    #[test]
+    fn test_max_opposition() {
+        let rule = DecisionRule::MaxOpposition(2);
+        assert_eq!(rule.evaluate(&tally(1, 2), 3, false), Decision::Approved);
+        assert_eq!(
+            rule.evaluate(&tally(5, 3), 3, false),
+            Decision::Rejected(RejectionReason::TooManyOppositions { count: 3, max: 2 })
+        );
+    }
+
+    #[test]
+    fn test_abstentions() {
+        let rule = DecisionRule::Supermajority(50);
+        let tally = VoteTally {
+            yes: 2,
+            no: 1,
+            abstain: 3,
+        };
+        assert_eq!(
+            rule.evaluate(&tally, 5, false),
+            Decision::Rejected(RejectionReason::QuorumNotReached(3, 5))
+        );
+        assert_eq!(rule.evaluate(&tally, 5, true), Decision::Approved);
+    }
+
+    #[test]
+    fn test_is_decided() {
+        // a veto cannot be overturned
+        assert!(DecisionRule::Veto.is_decided(&tally(1, 1), 5, false, 10));
+        assert!(!DecisionRule::Veto.is_decided(&tally(5, 0), 5, false, 1));
+        assert!(DecisionRule::Veto.is_decided(&tally(5, 0), 5, false, 0));
+
+        // the remaining votes could still tip the majority
+        assert!(!DecisionRule::Majority.is_decided(&tally(4, 2), 5, false, 2));
+        assert!(DecisionRule::Majority.is_decided(&tally(5, 2), 5, false, 2));
+        assert!(DecisionRule::Majority.is_decided(&tally(1, 5), 5, false, 3));
+
+        // the quorum can no longer be reached
+        assert!(DecisionRule::Majority.is_decided(&tally(2, 0), 5, false, 2));
+        assert!(!DecisionRule::Majority.is_decided(&tally(2, 0), 5, false, 3));
+    }
+
+    #[test]
+    fn test_parse_roundtrip() {
+        for rule in [
+            DecisionRule::Veto,
+            DecisionRule::Majority,
+            DecisionRule::Supermajority(67),
+            DecisionRule::MaxOpposition(3),
+        ] {
+            assert_eq!(rule.to_string().parse::<DecisionRule>().unwrap(), rule);
+        }
+
+        assert_eq!(
+            " Supermajority: 75% ".parse::<DecisionRule>().unwrap(),
+            DecisionRule::Supermajority(75)
+        );
+        assert!("supermajority".parse::<DecisionRule>().is_err());
+        assert!("supermajority:101".parse::<DecisionRule>().is_err());
+        assert!("veto:1".parse::<DecisionRule>().is_err());
+        assert!("unanimity".parse::<DecisionRule>().is_err());
+    }
+}
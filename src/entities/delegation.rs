@@ -0,0 +1,160 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+use crate::{
+    error::Error,
+    util::serenity::{GuildId, UserId},
+};
+
+use super::PollVote;
+
+/// A member of a guild delegating their vote to another member.
+#[derive(Debug, sqlx::FromRow)]
+pub struct Delegation {
+    pub guild_id: GuildId,
+    pub delegator: UserId,
+    pub delegate: UserId,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Delegation {
+    pub async fn create_or_update<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+        delegator: &UserId,
+        delegate: &UserId,
+    ) -> Result<Self, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO delegation (guild_id, delegator, delegate)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id, delegator) DO UPDATE SET
+                    delegate = EXCLUDED.delegate
+                RETURNING *;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(delegator)
+        .bind(delegate)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn delete<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+        delegator: &UserId,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                DELETE FROM delegation
+                WHERE guild_id = $1 AND delegator = $2
+                RETURNING *;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(delegator)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn find_by_guild_id<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+    ) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM delegation
+                WHERE guild_id = $1;
+            "#,
+        )
+        .bind(guild_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
+
+/// Resolves the votes inherited by the `non_voters` by following their `delegations` until reaching
+/// a member who voted. Returns the delegator, the member whose vote was inherited and their vote.
+///
+/// Delegations are followed transitively through other non-voters, a chain that loops back on
+/// itself or ends without reaching a voter does not inherit anything.
+pub fn resolve_delegated_votes<K>(
+    non_voters: impl IntoIterator<Item = K>,
+    votes: &HashMap<K, PollVote>,
+    delegations: &HashMap<K, K>,
+) -> Vec<(K, K, PollVote)>
+where
+    K: Copy + Eq + Hash,
+{
+    let mut res = Vec::new();
+
+    for delegator in non_voters {
+        let mut visited = HashSet::from([delegator]);
+        let mut current = delegator;
+
+        while let Some(&delegate) = delegations.get(&current) {
+            if !visited.insert(delegate) {
+                break;
+            }
+
+            if let Some(&vote) = votes.get(&delegate) {
+                res.push((delegator, delegate, vote));
+                break;
+            }
+
+            current = delegate;
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_delegated_votes_transitively() {
+        let votes = HashMap::from([(1, PollVote::Yes)]);
+        let delegations = HashMap::from([(2, 1), (3, 2), (4, 5)]);
+
+        let res = resolve_delegated_votes([2, 3, 4], &votes, &delegations);
+
+        assert_eq!(res.len(), 2);
+        assert!(matches!(res[0], (2, 1, PollVote::Yes)));
+        assert!(matches!(res[1], (3, 1, PollVote::Yes)));
+    }
+
+    #[test]
+    fn test_resolve_delegated_votes_with_cycle() {
+        let votes = HashMap::from([(4, PollVote::No)]);
+        let delegations = HashMap::from([(1, 2), (2, 3), (3, 1), (5, 3)]);
+
+        let res = resolve_delegated_votes([1, 2, 3, 5], &votes, &delegations);
+
+        assert!(res.is_empty());
+    }
+}
@@ -1,11 +1,14 @@
-use chrono::{DateTime, Utc};
-use sqlx::{Executor, Postgres};
+use chrono::{DateTime, Duration, Utc};
+use serenity::model::guild::Member;
+use sqlx::{postgres::types::PgInterval, Executor, Postgres};
 
 use crate::{
     error::Error,
-    util::serenity::{ChannelId, GuildId},
+    util::serenity::{ChannelId, GuildId, RoleId},
 };
 
+use super::{DecisionRule, VoteVisibility};
+
 #[derive(Debug, sqlx::FromRow)]
 pub struct Guild {
     pub id: GuildId,
@@ -13,11 +16,105 @@ pub struct Guild {
     pub invite_channel_id: ChannelId,
     /// The minimum number of votes required to consider a vote valid (0.0 - 1.0).
     pub invite_poll_quorum: f32,
+    /// The rule used to decide the outcome of an invite poll.
+    pub invite_poll_decision_rule: DecisionRule,
+    /// Whether abstentions count toward the quorum, shared by invite, kick and community polls.
+    pub abstentions_count_toward_quorum: bool,
+    /// The minimum number of votes required to consider a kick poll valid (0.0 - 1.0).
+    pub kick_poll_quorum: f32,
+    /// The rule used to decide the outcome of a kick poll.
+    pub kick_poll_decision_rule: DecisionRule,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// The default visibility of the votes of new invite polls.
+    pub vote_visibility: VoteVisibility,
+    /// How long after a denied or cancelled invite poll the same user cannot be proposed again.
+    pub invite_poll_deny_cooldown: PgInterval,
+    /// The maximum number of open invite polls per inviter, zero disables the limit.
+    pub invite_poll_max_open_per_inviter: i32,
+    /// The maximum number of invite polls per inviter within `invite_poll_rate_limit_window`,
+    /// zero disables the limit.
+    pub invite_poll_max_per_inviter: i32,
+    pub invite_poll_rate_limit_window: PgInterval,
+    /// Members with this role can manage the bot like administrators.
+    pub manager_role_id: Option<RoleId>,
+    /// Only members with one of these roles can vote on invite polls, everyone if empty.
+    pub voter_role_ids: Vec<RoleId>,
+    /// Only members with one of these roles can open invite polls, everyone if empty.
+    pub proposer_role_ids: Vec<RoleId>,
+    /// How many days members have to be part of the guild before they can vote on invite polls.
+    pub min_days_to_vote: i32,
+    /// Whether invite polls close as soon as the remaining votes cannot change their outcome.
+    pub invite_poll_early_close: bool,
+    /// How long before the end of invite polls reminders are posted.
+    pub invite_poll_reminders: Vec<PgInterval>,
+    /// The role mentioned by the reminders.
+    pub reminder_role_id: Option<RoleId>,
+    /// The channel where the results of invite polls are announced.
+    pub results_channel_id: Option<ChannelId>,
+    /// The channel where the audit events are mirrored.
+    pub log_channel_id: Option<ChannelId>,
+    /// Whether members can change their vote on an open invite poll.
+    pub allow_vote_changes: bool,
+}
+
+/// Optional settings, `None` values are left unchanged and `Some(None)` clears a nullable one.
+#[derive(Debug, Default)]
+pub struct GuildSettingsUpdate {
+    pub invite_poll_decision_rule: Option<DecisionRule>,
+    pub abstentions_count_toward_quorum: Option<bool>,
+    pub kick_poll_quorum: Option<f32>,
+    pub kick_poll_decision_rule: Option<DecisionRule>,
+    pub vote_visibility: Option<VoteVisibility>,
+    pub invite_poll_deny_cooldown: Option<PgInterval>,
+    pub invite_poll_max_open_per_inviter: Option<i32>,
+    pub invite_poll_max_per_inviter: Option<i32>,
+    pub invite_poll_rate_limit_window: Option<PgInterval>,
+    pub manager_role_id: Option<Option<RoleId>>,
+    pub voter_role_ids: Option<Vec<RoleId>>,
+    pub proposer_role_ids: Option<Vec<RoleId>>,
+    pub min_days_to_vote: Option<i32>,
+    pub invite_poll_early_close: Option<bool>,
+    pub invite_poll_reminders: Option<Vec<PgInterval>>,
+    pub reminder_role_id: Option<Option<RoleId>>,
+    pub results_channel_id: Option<Option<ChannelId>>,
+    pub log_channel_id: Option<Option<ChannelId>>,
+    pub allow_vote_changes: Option<bool>,
+}
+
+/// Whether `member` has one of the `role_ids`, every member does if there are none.
+fn has_any_role(role_ids: &[RoleId], member: &Member) -> bool {
+    role_ids.is_empty()
+        || role_ids
+            .iter()
+            .any(|role_id| member.roles.contains(role_id))
 }
 
 impl Guild {
+    /// Whether `member` has a role allowed to vote on invite polls.
+    pub fn has_voter_role(&self, member: &Member) -> bool {
+        has_any_role(&self.voter_role_ids, member)
+    }
+
+    /// When `member` will have been part of the guild long enough to vote on invite polls, if
+    /// they have not been yet.
+    pub fn voting_allowed_at(&self, member: &Member) -> Option<DateTime<Utc>> {
+        let joined_at = *member.joined_at?;
+        let allowed_at = joined_at + Duration::days(self.min_days_to_vote.into());
+
+        (allowed_at > Utc::now()).then_some(allowed_at)
+    }
+
+    /// Whether `member` can vote on invite polls.
+    pub fn is_eligible_voter(&self, member: &Member) -> bool {
+        self.has_voter_role(member) && self.voting_allowed_at(member).is_none()
+    }
+
+    /// Whether `member` can open invite polls.
+    pub fn is_eligible_proposer(&self, member: &Member) -> bool {
+        has_any_role(&self.proposer_role_ids, member)
+    }
+
     pub async fn create_or_update<'c, E>(
         executor: E,
         id: &GuildId,
@@ -63,4 +160,70 @@ impl Guild {
 
         Ok(res)
     }
+
+    pub async fn update_settings<'c, E>(
+        &mut self,
+        executor: E,
+        settings: &GuildSettingsUpdate,
+    ) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE guild
+                SET
+                    invite_poll_decision_rule = COALESCE($2, invite_poll_decision_rule),
+                    abstentions_count_toward_quorum = COALESCE($3, abstentions_count_toward_quorum),
+                    kick_poll_quorum = COALESCE($4, kick_poll_quorum),
+                    kick_poll_decision_rule = COALESCE($5, kick_poll_decision_rule),
+                    vote_visibility = COALESCE($6, vote_visibility),
+                    invite_poll_deny_cooldown = COALESCE($7, invite_poll_deny_cooldown),
+                    invite_poll_max_open_per_inviter = COALESCE($8, invite_poll_max_open_per_inviter),
+                    invite_poll_max_per_inviter = COALESCE($9, invite_poll_max_per_inviter),
+                    invite_poll_rate_limit_window = COALESCE($10, invite_poll_rate_limit_window),
+                    manager_role_id = CASE WHEN $21 THEN $11 ELSE manager_role_id END,
+                    voter_role_ids = COALESCE($12, voter_role_ids),
+                    proposer_role_ids = COALESCE($13, proposer_role_ids),
+                    min_days_to_vote = COALESCE($14, min_days_to_vote),
+                    invite_poll_early_close = COALESCE($15, invite_poll_early_close),
+                    invite_poll_reminders = COALESCE($16, invite_poll_reminders),
+                    reminder_role_id = CASE WHEN $22 THEN $17 ELSE reminder_role_id END,
+                    results_channel_id = CASE WHEN $23 THEN $18 ELSE results_channel_id END,
+                    log_channel_id = CASE WHEN $24 THEN $19 ELSE log_channel_id END,
+                    allow_vote_changes = COALESCE($20, allow_vote_changes)
+                WHERE id = $1
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(settings.invite_poll_decision_rule)
+        .bind(settings.abstentions_count_toward_quorum)
+        .bind(settings.kick_poll_quorum)
+        .bind(settings.kick_poll_decision_rule)
+        .bind(settings.vote_visibility)
+        .bind(settings.invite_poll_deny_cooldown.as_ref())
+        .bind(settings.invite_poll_max_open_per_inviter)
+        .bind(settings.invite_poll_max_per_inviter)
+        .bind(settings.invite_poll_rate_limit_window.as_ref())
+        .bind(settings.manager_role_id.as_ref().and_then(Option::as_ref))
+        .bind(settings.voter_role_ids.as_deref())
+        .bind(settings.proposer_role_ids.as_deref())
+        .bind(settings.min_days_to_vote)
+        .bind(settings.invite_poll_early_close)
+        .bind(settings.invite_poll_reminders.as_deref())
+        .bind(settings.reminder_role_id.as_ref().and_then(Option::as_ref))
+        .bind(settings.results_channel_id.as_ref().and_then(Option::as_ref))
+        .bind(settings.log_channel_id.as_ref().and_then(Option::as_ref))
+        .bind(settings.allow_vote_changes)
+        .bind(settings.manager_role_id.is_some())
+        .bind(settings.reminder_role_id.is_some())
+        .bind(settings.results_channel_id.is_some())
+        .bind(settings.log_channel_id.is_some())
+        .fetch_one(executor)
+        .await?;
+
+        *self = res;
+        Ok(())
+    }
 }
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serenity::model::guild::Member;
+use sqlx::{Executor, PgConnection, Postgres};
+
+use crate::{
+    error::Error,
+    util::serenity::{GuildId, RoleId},
+};
+
+/// The weight of the votes of members with a role.
+#[derive(Debug, sqlx::FromRow)]
+pub struct GuildVoteWeight {
+    pub guild_id: GuildId,
+    pub role_id: RoleId,
+    pub weight: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The weight of a member without any weighted role.
+pub const DEFAULT_VOTE_WEIGHT: i32 = 1;
+
+impl GuildVoteWeight {
+    /// Replaces the vote weights of a guild with `weights`.
+    pub async fn replace(
+        conn: &mut PgConnection,
+        guild_id: &GuildId,
+        weights: &[(RoleId, i32)],
+    ) -> Result<Vec<Self>, Error> {
+        sqlx::query(
+            r#"
+                DELETE FROM guild_vote_weight
+                WHERE guild_id = $1;
+            "#,
+        )
+        .bind(guild_id)
+        .execute(&mut *conn)
+        .await?;
+
+        let mut res = Vec::with_capacity(weights.len());
+        for (role_id, weight) in weights {
+            let vote_weight = sqlx::query_as::<_, Self>(
+                r#"
+                    INSERT INTO guild_vote_weight (guild_id, role_id, weight)
+                    VALUES ($1, $2, $3)
+                    RETURNING *;
+                "#,
+            )
+            .bind(guild_id)
+            .bind(role_id)
+            .bind(weight)
+            .fetch_one(&mut *conn)
+            .await?;
+
+            res.push(vote_weight);
+        }
+
+        Ok(res)
+    }
+
+    pub async fn find_by_guild_id<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+    ) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM guild_vote_weight
+                WHERE guild_id = $1
+                ORDER BY weight DESC;
+            "#,
+        )
+        .bind(guild_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
+
+/// The weight of the vote of `member`, the highest among the weights of their roles.
+pub fn vote_weight(weights: &[GuildVoteWeight], member: &Member) -> i32 {
+    weights
+        .iter()
+        .filter(|weight| member.roles.contains(&weight.role_id))
+        .map(|weight| weight.weight)
+        .max()
+        .unwrap_or(DEFAULT_VOTE_WEIGHT)
+}
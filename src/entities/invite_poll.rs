@@ -1,44 +1,15 @@
-use std::{fmt::Display, str::FromStr, time::Duration};
+use std::time::Duration;
 
-use base64::{display::Base64Display, Engine};
 use chrono::{DateTime, Utc};
 use serenity::model::prelude::Message;
 use sqlx::{postgres::types::PgInterval, Executor, PgExecutor, Postgres};
-use uuid::Uuid;
 
 use crate::{
     error::Error,
     util::serenity::{ChannelId, GuildId, MessageId, UserId},
 };
 
-use super::InvitePollOutcome;
-
-static BASE64: base64::engine::GeneralPurpose = base64::engine::general_purpose::STANDARD_NO_PAD;
-
-#[derive(Clone, Debug, sqlx::Type)]
-#[sqlx(transparent)]
-pub struct InvitePollId(pub Uuid);
-
-impl Display for InvitePollId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Base64Display::new(self.0.as_bytes(), &BASE64).fmt(f)
-    }
-}
-
-impl FromStr for InvitePollId {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let buf = BASE64
-            .decode(s)
-            .map_err(|err| Error::InvitePollIdInvalid(s.to_owned(), Box::new(err)))?;
-
-        let id = Uuid::from_slice(&buf)
-            .map_err(|err| Error::InvitePollIdInvalid(s.to_owned(), Box::new(err)))?;
-
-        Ok(Self(id))
-    }
-}
+use super::{InvitePollId, InvitePollOutcome, VoteVisibility};
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct InvitePoll {
@@ -53,14 +24,21 @@ pub struct InvitePoll {
     pub ends_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub vote_visibility: VoteVisibility,
+    /// When the poll was last closed, `None` while it is open.
+    pub closed_at: Option<DateTime<Utc>>,
 }
 
+/// The unique index preventing multiple open polls for the same invitee.
+pub const INVITE_POLL_OPEN_INVITEE_KEY: &'static str = "invite_poll_open_invitee_key";
+
 impl InvitePoll {
     pub async fn create<'e, E>(
         executor: E,
         guild_id: &GuildId,
         inviter: &UserId,
         invitee: &UserId,
+        vote_visibility: Option<VoteVisibility>,
         duration: &Duration,
     ) -> Result<Self, Error>
     where
@@ -70,8 +48,14 @@ impl InvitePoll {
 
         let res = sqlx::query_as::<_, Self>(
             r#"
-                INSERT INTO invite_poll (guild_id, inviter, invitee, ends_at)
-                VALUES ($1, $2, $3, now() + $4)
+                INSERT INTO invite_poll (guild_id, inviter, invitee, ends_at, vote_visibility)
+                VALUES (
+                    $1,
+                    $2,
+                    $3,
+                    now() + $4,
+                    COALESCE($5, (SELECT vote_visibility FROM guild WHERE id = $1), 'secret')
+                )
                 RETURNING *;
             "#,
         )
@@ -79,12 +63,181 @@ impl InvitePoll {
         .bind(inviter)
         .bind(invitee)
         .bind(duration)
+        .bind(vote_visibility)
         .fetch_one(executor)
         .await?;
 
         Ok(res)
     }
 
+    pub async fn find_open_by_invitee<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+        invitee: &UserId,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM invite_poll
+                WHERE guild_id = $1 AND invitee = $2 AND outcome IS NULL;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(invitee)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Locks an open poll until the end of the transaction, so that only one task can close it.
+    pub async fn find_open_for_update<'c, E>(
+        executor: E,
+        id: &InvitePollId,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM invite_poll
+                WHERE id = $1 AND outcome IS NULL
+                FOR NO KEY UPDATE;
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Prevents an open poll from being closed until the end of the transaction.
+    pub async fn find_open_for_share<'c, E>(
+        executor: E,
+        id: &InvitePollId,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM invite_poll
+                WHERE id = $1 AND outcome IS NULL
+                FOR SHARE;
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// When the `invitee` can be proposed again if a recent poll denied them, according to the
+    /// guild's cooldown.
+    ///
+    /// Cancelled polls start the cooldown as well, otherwise an inviter could cancel a poll heading
+    /// for a denial and propose the same user again right away.
+    pub async fn find_deny_cooldown_end<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+        invitee: &UserId,
+    ) -> Result<Option<DateTime<Utc>>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_scalar::<_, DateTime<Utc>>(
+            r#"
+                SELECT ip.closed_at + g.invite_poll_deny_cooldown
+                FROM invite_poll AS ip
+                JOIN guild AS g ON g.id = ip.guild_id
+                WHERE
+                    ip.guild_id = $1
+                    AND ip.invitee = $2
+                    AND ip.outcome IN ('deny', 'cancelled')
+                    AND ip.closed_at + g.invite_poll_deny_cooldown > now()
+                ORDER BY ip.closed_at DESC
+                LIMIT 1;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(invitee)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// When the `inviter` can open a new poll if they reached one of the guild's rate limits.
+    pub async fn find_rate_limit_reset<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+        inviter: &UserId,
+    ) -> Result<Option<DateTime<Utc>>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        // `GREATEST` ignores the limits which have not been reached
+        let res = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            r#"
+                SELECT GREATEST(
+                    CASE
+                        WHEN g.invite_poll_max_open_per_inviter > 0
+                            AND open.count >= g.invite_poll_max_open_per_inviter
+                        THEN open.reset_at
+                    END,
+                    CASE
+                        WHEN g.invite_poll_max_per_inviter > 0
+                            AND recent.count >= g.invite_poll_max_per_inviter
+                        THEN recent.reset_at
+                    END
+                )
+                FROM guild AS g
+                CROSS JOIN LATERAL (
+                    SELECT count(*) AS count, min(ip.ends_at) AS reset_at
+                    FROM invite_poll AS ip
+                    WHERE ip.guild_id = g.id AND ip.inviter = $2 AND ip.outcome IS NULL
+                ) AS open
+                CROSS JOIN LATERAL (
+                    SELECT
+                        count(*) AS count,
+                        min(ip.created_at) + g.invite_poll_rate_limit_window AS reset_at
+                    FROM invite_poll AS ip
+                    WHERE
+                        ip.guild_id = g.id
+                        AND ip.inviter = $2
+                        AND ip.created_at > now() - g.invite_poll_rate_limit_window
+                ) AS recent
+                WHERE g.id = $1;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(inviter)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res.flatten())
+    }
+
+    /// The link to the poll message, if it was sent.
+    pub fn message_url(&self) -> Option<String> {
+        match (&self.channel_id, &self.message_id) {
+            (Some(channel_id), Some(message_id)) => Some(format!(
+                "https://discord.com/channels/{}/{}/{}",
+                self.guild_id.get(),
+                channel_id.get(),
+                message_id.get()
+            )),
+            _ => None,
+        }
+    }
+
     pub async fn update_message<'e, E>(
         &mut self,
         executor: E,
@@ -111,6 +264,61 @@ impl InvitePoll {
         Ok(())
     }
 
+    /// Pushes the end of an open poll back by `duration`, fails with `InvitePollClosed` if it was
+    /// closed in the meantime.
+    pub async fn extend<'c, E>(&mut self, executor: E, duration: &Duration) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let duration = PgInterval::try_from(*duration).map_err(sqlx::Error::Decode)?;
+
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE invite_poll
+                SET ends_at = ends_at + $2
+                WHERE id = $1 AND outcome IS NULL
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(duration)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| Error::InvitePollClosed(self.id.clone()))?;
+
+        *self = res;
+        Ok(())
+    }
+
+    /// Clears the outcome of a closed poll, keeping it open for at least `duration`.
+    pub async fn reopen<'c, E>(&mut self, executor: E, duration: &Duration) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let duration = PgInterval::try_from(*duration).map_err(sqlx::Error::Decode)?;
+
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE invite_poll
+                SET
+                    outcome = NULL,
+                    message = NULL,
+                    closed_at = NULL,
+                    ends_at = GREATEST(ends_at, now() + $2)
+                WHERE id = $1
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(duration)
+        .fetch_one(executor)
+        .await?;
+
+        *self = res;
+        Ok(())
+    }
+
+    /// Sets the outcome of an open poll, fails with `InvitePollClosed` if it was already closed.
     pub async fn close<'c, E>(
         &mut self,
         executor: E,
@@ -123,16 +331,17 @@ impl InvitePoll {
         let res = sqlx::query_as::<_, Self>(
             r#"
                 UPDATE invite_poll
-                SET outcome = $2, message = $3
-                WHERE id = $1
+                SET outcome = $2, message = $3, closed_at = now()
+                WHERE id = $1 AND outcome IS NULL
                 RETURNING *;
             "#,
         )
         .bind(&self.id)
         .bind(outcome)
         .bind(message)
-        .fetch_one(executor)
-        .await?;
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| Error::InvitePollClosed(self.id.clone()))?;
 
         *self = res;
         Ok(())
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+use crate::error::Error;
+
+use super::InvitePollId;
+
+/// An invite poll whose message is stale and has to be re-rendered.
+#[derive(Debug, sqlx::FromRow)]
+pub struct InvitePollMessageRefresh {
+    pub invite_poll_id: InvitePollId,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InvitePollMessageRefresh {
+    pub async fn find_all<'c, E>(executor: E) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM invite_poll_message_refresh
+                ORDER BY created_at;
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn delete<'c, E>(executor: E, invite_poll_id: &InvitePollId) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query(
+            r#"
+                DELETE FROM invite_poll_message_refresh
+                WHERE invite_poll_id = $1;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
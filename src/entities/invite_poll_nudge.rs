@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::types::PgInterval, Executor, Postgres};
+
+use crate::error::Error;
+
+use super::{InvitePoll, InvitePollId};
+
+/// Records that the voters of an invite poll were nudged to vote.
+#[derive(Debug, sqlx::FromRow)]
+pub struct InvitePollNudge {
+    pub invite_poll_id: InvitePollId,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InvitePollNudge {
+    /// Records the nudge, returns `None` if the voters were already nudged.
+    pub async fn create<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO invite_poll_nudge (invite_poll_id)
+                VALUES ($1)
+                ON CONFLICT (invite_poll_id) DO NOTHING
+                RETURNING *;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Forgets the nudge of an invite poll, so that they are sent again once it is reopened.
+    pub async fn delete_by_invite_poll_id<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+    ) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query(
+            r#"
+                DELETE FROM invite_poll_nudge
+                WHERE invite_poll_id = $1;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Finds the open invite polls ending within `before` whose voters were not nudged yet, skipping
+    /// the ones that were shorter than `before` to begin with.
+    pub async fn find_due<'c, E>(executor: E, before: &Duration) -> Result<Vec<InvitePoll>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let before = PgInterval::try_from(*before).map_err(sqlx::Error::Decode)?;
+
+        let res = sqlx::query_as::<_, InvitePoll>(
+            r#"
+                SELECT ip.*
+                FROM invite_poll AS ip
+                WHERE
+                    ip.outcome IS NULL
+                    AND ip.ends_at > now()
+                    AND ip.ends_at - $1 <= now()
+                    AND ip.ends_at - $1 > ip.created_at
+                    AND NOT EXISTS (
+                        SELECT 1
+                        FROM invite_poll_nudge AS ipn
+                        WHERE ipn.invite_poll_id = ip.id
+                    );
+            "#,
+        )
+        .bind(before)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::types::PgInterval, Executor, Postgres};
+
+use crate::{error::Error, util::serenity::RoleId};
+
+use super::{InvitePoll, InvitePollId};
+
+/// A reminder posted before the end of an invite poll.
+#[derive(Debug, sqlx::FromRow)]
+pub struct InvitePollReminder {
+    pub invite_poll_id: InvitePollId,
+    /// How long before the end of the poll the reminder was due.
+    pub remind_before: PgInterval,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A reminder of an open invite poll that is due but has not been posted yet.
+#[derive(Debug, sqlx::FromRow)]
+pub struct DueInvitePollReminder {
+    #[sqlx(flatten)]
+    pub invite_poll: InvitePoll,
+    pub remind_before: PgInterval,
+    /// The role to mention in the reminder.
+    pub reminder_role_id: Option<RoleId>,
+}
+
+impl InvitePollReminder {
+    /// Records the reminder as posted, returns `None` if it already was.
+    pub async fn create<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+        remind_before: &PgInterval,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO invite_poll_reminder (invite_poll_id, remind_before)
+                VALUES ($1, $2)
+                ON CONFLICT (invite_poll_id, remind_before) DO NOTHING
+                RETURNING *;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .bind(remind_before)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Forgets the reminders of an invite poll, so that they are sent again once it is reopened.
+    pub async fn delete_by_invite_poll_id<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+    ) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query(
+            r#"
+                DELETE FROM invite_poll_reminder
+                WHERE invite_poll_id = $1;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Finds the reminders of the open invite polls that are due, skipping the ones that were due
+    /// before the poll was even created.
+    pub async fn find_due<'c, E>(executor: E) -> Result<Vec<DueInvitePollReminder>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, DueInvitePollReminder>(
+            r#"
+                SELECT ip.*, r.remind_before, g.reminder_role_id
+                FROM invite_poll AS ip
+                JOIN guild AS g ON g.id = ip.guild_id
+                CROSS JOIN LATERAL unnest(g.invite_poll_reminders) AS r (remind_before)
+                WHERE
+                    ip.outcome IS NULL
+                    AND ip.message_id IS NOT NULL
+                    AND ip.ends_at > now()
+                    AND ip.ends_at - r.remind_before <= now()
+                    AND ip.ends_at - r.remind_before > ip.created_at
+                    AND NOT EXISTS (
+                        SELECT 1
+                        FROM invite_poll_reminder AS ipr
+                        WHERE ipr.invite_poll_id = ip.id AND ipr.remind_before = r.remind_before
+                    )
+                ORDER BY ip.ends_at;
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+use crate::{error::Error, util::serenity::UserId};
+
+use super::{InvitePollId, InvitePollVoteSubmission, PollVote};
+
+/// A vote cast or changed on an invite poll, kept as an append-only history.
+#[derive(Debug, sqlx::FromRow)]
+pub struct InvitePollVoteEvent {
+    pub id: i64,
+    pub invite_poll_id: InvitePollId,
+    pub user_id: UserId,
+    pub vote: PollVote,
+    /// The vote of the user before this event, `None` on their first vote.
+    pub previous_vote: Option<PollVote>,
+    pub weight: i32,
+    pub created_at: DateTime<Utc>,
+    /// The member whose vote was inherited through a delegation when the poll closed.
+    pub inherited_from: Option<UserId>,
+}
+
+impl InvitePollVoteEvent {
+    /// Records the `submission` which replaced the `previous_vote` of the voter, if any.
+    ///
+    /// Must be executed in the transaction which locked the previous submission, so that
+    /// concurrent votes are recorded in order.
+    pub async fn create<'c, E>(
+        executor: E,
+        submission: &InvitePollVoteSubmission,
+        previous_vote: Option<PollVote>,
+    ) -> Result<Self, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO invite_poll_vote_event (
+                    invite_poll_id,
+                    user_id,
+                    vote,
+                    previous_vote,
+                    weight,
+                    inherited_from
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING *;
+            "#,
+        )
+        .bind(&submission.invite_poll_id)
+        .bind(&submission.user_id)
+        .bind(submission.vote)
+        .bind(previous_vote)
+        .bind(submission.weight)
+        .bind(submission.inherited_from.as_ref())
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
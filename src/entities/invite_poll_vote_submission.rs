@@ -3,41 +3,230 @@ use sqlx::{Executor, Postgres};
 
 use crate::{error::Error, util::serenity::UserId};
 
-use super::{InvitePollId, InvitePollVote};
+use super::{InvitePollId, PollVote};
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct InvitePollVoteSubmission {
     pub invite_poll_id: InvitePollId,
     pub user_id: UserId,
-    pub vote: InvitePollVote,
+    pub vote: PollVote,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// The weight of the voter when the vote was submitted.
+    pub weight: i32,
+    /// The member whose vote was inherited through a delegation when the poll closed.
+    pub inherited_from: Option<UserId>,
+    /// The optional reason given by the voter when voting no.
+    pub reason: Option<String>,
 }
 
 impl InvitePollVoteSubmission {
+    /// Records the first vote of `user_id`, returns `None` if they already voted.
+    pub async fn create<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+        user_id: &UserId,
+        vote: PollVote,
+        weight: i32,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO invite_poll_vote_submission (invite_poll_id, user_id, vote, weight)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (invite_poll_id, user_id) DO NOTHING
+                RETURNING *;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .bind(user_id)
+        .bind(vote)
+        .bind(weight)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
     pub async fn create_or_update<'c, E>(
         executor: E,
         invite_poll_id: &InvitePollId,
         user_id: &UserId,
-        vote: InvitePollVote,
+        vote: PollVote,
+        weight: i32,
     ) -> Result<Self, Error>
     where
         E: Executor<'c, Database = Postgres>,
     {
         let res = sqlx::query_as::<_, Self>(
             r#"
-                INSERT INTO invite_poll_vote_submission (invite_poll_id, user_id, vote)
-                VALUES ($1, $2, $3)
-                ON CONFLICT (invite_poll_id, user_id) DO UPDATE SET vote = EXCLUDED.vote
+                INSERT INTO invite_poll_vote_submission (invite_poll_id, user_id, vote, weight)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (invite_poll_id, user_id) DO UPDATE SET
+                    vote = EXCLUDED.vote,
+                    weight = EXCLUDED.weight,
+                    inherited_from = NULL,
+                    reason = NULL
                 RETURNING *;
             "#,
         )
         .bind(invite_poll_id)
         .bind(user_id)
         .bind(vote)
+        .bind(weight)
         .fetch_one(executor)
         .await?;
 
         Ok(res)
     }
+
+    /// Sets the reason of the vote of `user_id`, as long as they are still voting no.
+    pub async fn update_reason<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+        user_id: &UserId,
+        reason: Option<&str>,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE invite_poll_vote_submission
+                SET reason = $3
+                WHERE invite_poll_id = $1 AND user_id = $2 AND vote = 'no'
+                RETURNING *;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .bind(user_id)
+        .bind(reason)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Records the vote `user_id` inherited from `inherited_from`, unless they already voted.
+    pub async fn inherit<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+        user_id: &UserId,
+        vote: PollVote,
+        weight: i32,
+        inherited_from: &UserId,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO invite_poll_vote_submission (invite_poll_id, user_id, vote, weight, inherited_from)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (invite_poll_id, user_id) DO NOTHING
+                RETURNING *;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .bind(user_id)
+        .bind(vote)
+        .bind(weight)
+        .bind(inherited_from)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Deletes the votes inherited through delegations, e.g. when the poll is reopened.
+    pub async fn delete_inherited<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+    ) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        sqlx::query(
+            r#"
+                DELETE FROM invite_poll_vote_submission
+                WHERE invite_poll_id = $1 AND inherited_from IS NOT NULL;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_reasons_by_invite_poll_id<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+    ) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM invite_poll_vote_submission
+                WHERE invite_poll_id = $1 AND reason IS NOT NULL
+                ORDER BY created_at;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Locks the vote of `user_id` until the end of the transaction.
+    pub async fn find_by_invite_poll_id_and_user_id_for_update<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+        user_id: &UserId,
+    ) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM invite_poll_vote_submission
+                WHERE invite_poll_id = $1 AND user_id = $2
+                FOR UPDATE;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .bind(user_id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn find_by_invite_poll_id<'c, E>(
+        executor: E,
+        invite_poll_id: &InvitePollId,
+    ) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM invite_poll_vote_submission
+                WHERE invite_poll_id = $1
+                ORDER BY created_at;
+            "#,
+        )
+        .bind(invite_poll_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
 }
@@ -1,3 +1,4 @@
+use async_trait::async_trait;
 use serenity::{
     all::ButtonStyle,
     builder::{CreateActionRow, CreateButton, CreateEmbed},
@@ -6,23 +7,49 @@ use serenity::{
 use sqlx::{Executor, Postgres};
 
 use crate::{
-    action::POLL_ID_FIELD_NAME,
     error::Error,
     util::{
-        colors, emojis, serenity::MessageRenderer, DiscordTimestamp, DiscordTimestampStyle,
-        ProgressBar,
+        colors, emojis,
+        serenity::{ChannelId, MessageId, MessageRenderer},
     },
 };
 
-use super::{InvitePoll, InvitePollId, InvitePollOutcome};
+use super::{
+    render, InvitePoll, InvitePollId, InvitePollOutcome, InvitePollVoteSubmission, PollMessage,
+    VoteTally,
+};
+
+fn render_outcome(outcome: InvitePollOutcome) -> String {
+    match outcome {
+        InvitePollOutcome::Allow => [emojis::CHECK_MARK_BUTTON, " Allowed"].concat(),
+        InvitePollOutcome::Deny => [emojis::NO_ENTRY, " Denied"].concat(),
+        InvitePollOutcome::Cancelled => [emojis::PROHIBITED, " Cancelled"].concat(),
+    }
+}
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct InvitePollWithVoteCount {
     #[sqlx(flatten)]
     pub invite_poll: InvitePoll,
 
+    /// The sum of the weights of the `yes` votes.
     pub yes_count: i64,
+    /// The sum of the weights of the `no` votes.
     pub no_count: i64,
+    /// The sum of the weights of the `abstain` votes.
+    pub abstain_count: i64,
+    /// The number of votes inherited through delegations.
+    pub delegated_count: i64,
+    /// The number of members who voted, regardless of their weight.
+    pub voter_count: i64,
+
+    /// The individual votes, only loaded by `load_votes` when the poll reveals them.
+    #[sqlx(skip)]
+    pub votes: Vec<InvitePollVoteSubmission>,
+
+    /// The votes with a reason, only loaded by `load_votes` once the poll is closed.
+    #[sqlx(skip)]
+    pub reasons: Vec<InvitePollVoteSubmission>,
 }
 
 impl InvitePollWithVoteCount {
@@ -61,6 +88,109 @@ impl InvitePollWithVoteCount {
         Ok(res)
     }
 
+    /// Loads the individual votes if the visibility of the poll reveals them, and the reasons of the
+    /// no votes once the poll is closed.
+    pub async fn load_votes<'c, E>(&mut self, executor: E) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres> + Copy,
+    {
+        let closed = self.invite_poll.outcome.is_some();
+        if self.invite_poll.vote_visibility.reveals_votes(closed) {
+            self.votes =
+                InvitePollVoteSubmission::find_by_invite_poll_id(executor, &self.invite_poll.id)
+                    .await?;
+        }
+        if closed {
+            self.reasons = InvitePollVoteSubmission::find_reasons_by_invite_poll_id(
+                executor,
+                &self.invite_poll.id,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders the reasons of the no votes, naming the voters only if the poll reveals them.
+    pub fn render_reasons(&self) -> String {
+        let closed = self.invite_poll.outcome.is_some();
+        let reveal = self.invite_poll.vote_visibility.reveals_votes(closed);
+
+        render::reason_list(self.reasons.iter().filter_map(|submission| {
+            let user_id = reveal.then_some(&submission.user_id);
+            submission.reason.as_deref().map(|reason| (user_id, reason))
+        }))
+    }
+
+    pub fn tally(&self) -> VoteTally {
+        VoteTally {
+            yes: self.yes_count,
+            no: self.no_count,
+            abstain: self.abstain_count,
+        }
+    }
+
+    /// Renders the summary of a closed poll announced in the results channel, `participation` is
+    /// the number of votes counted toward the `quorum`.
+    pub fn create_result_embed(&self, participation: i64, quorum: i64) -> CreateEmbed {
+        let mut embed = CreateEmbed::default()
+            .color(match self.invite_poll.outcome {
+                Some(InvitePollOutcome::Allow) => colors::DISCORD_GREEN,
+                Some(InvitePollOutcome::Deny) => colors::DISCORD_RED,
+                Some(InvitePollOutcome::Cancelled) => colors::DISCORD_YELLOW,
+                None => colors::DISCORD_BLURPLE,
+            })
+            .title("Invite Poll Result")
+            .field("User", self.invite_poll.invitee.to_string(), true)
+            .field(
+                "Outcome",
+                self.invite_poll
+                    .outcome
+                    .map(render_outcome)
+                    .unwrap_or_default(),
+                true,
+            )
+            .field(
+                "Quorum",
+                format!(
+                    "{} ({}/{})",
+                    if participation >= quorum {
+                        "Reached"
+                    } else {
+                        "Not Reached"
+                    },
+                    participation,
+                    quorum
+                ),
+                true,
+            )
+            .field(
+                format!("Weighted Votes ({} voters)", self.voter_count),
+                format!(
+                    "{} {} {} {} {} {}",
+                    emojis::LARGE_GREEN_CIRCLE,
+                    self.yes_count,
+                    emojis::LARGE_RED_CIRCLE,
+                    self.no_count,
+                    emojis::WHITE_CIRCLE,
+                    self.abstain_count
+                ),
+                true,
+            );
+
+        // the message of allowed polls may contain the invite
+        if let (Some(InvitePollOutcome::Deny), Some(message)) =
+            (self.invite_poll.outcome, self.invite_poll.message.as_ref())
+        {
+            embed = embed.field("Reason", message, true);
+        }
+        if let Some(url) = self.invite_poll.message_url() {
+            embed = embed.field("Poll", url, false);
+        }
+
+        embed
+    }
+
     pub async fn create_renderer(&self, ctx: Context) -> Result<MessageRenderer, Error> {
         let user = self.invite_poll.invitee.to_user(&ctx.http).await?;
 
@@ -71,121 +201,80 @@ impl InvitePollWithVoteCount {
                 .color(match self.invite_poll.outcome {
                     Some(InvitePollOutcome::Allow) => colors::DISCORD_GREEN,
                     Some(InvitePollOutcome::Deny) => colors::DISCORD_RED,
+                    Some(InvitePollOutcome::Cancelled) => colors::DISCORD_YELLOW,
                     None => colors::DISCORD_BLURPLE,
                 })
                 .title("Invite Poll")
                 .thumbnail(user.face());
 
             // row
-            embed = embed
-                .field(
-                    POLL_ID_FIELD_NAME,
-                    format!("`{}`", self.invite_poll.id),
-                    true,
-                )
-                .field("User", &user.name, true)
-                .field(
-                    "Status",
-                    if self.invite_poll.outcome.is_none() {
-                        "Open"
-                    } else {
-                        "Closed"
-                    },
-                    true,
-                );
+            embed = render::summary_row(
+                embed,
+                &self.invite_poll.id,
+                ("User", &user.name),
+                self.invite_poll.outcome.is_some(),
+            );
 
             // row
-            embed = embed
-                .field(
-                    "Created At",
-                    DiscordTimestamp::new(
-                        self.invite_poll.created_at,
-                        DiscordTimestampStyle::FullShort,
-                    ),
-                    true,
-                )
-                .field(
-                    "Ends At",
-                    {
-                        let ts = DiscordTimestamp::new(
-                            self.invite_poll.ends_at,
-                            DiscordTimestampStyle::FullShort,
-                        );
-
-                        format!(
-                            "{} ({})",
-                            ts,
-                            ts.with_style(DiscordTimestampStyle::Relative)
-                        )
-                    },
-                    true,
-                );
+            embed = render::schedule_fields(
+                embed,
+                self.invite_poll.created_at,
+                self.invite_poll.ends_at,
+            )
+            .field(
+                "Vote Visibility",
+                self.invite_poll.vote_visibility.to_string(),
+                true,
+            );
 
             // row
             embed = embed.field(
-                "Votes",
-                {
-                    let mut bar = ProgressBar::builder();
-                    bar.max(self.yes_count + self.no_count)
-                        .with_count(true)
-                        .with_percentage(true);
-
-                    format!(
-                        "{} {}\n{} {}",
-                        emojis::LARGE_GREEN_CIRCLE,
-                        bar.value(self.yes_count).build().unwrap(),
-                        emojis::LARGE_RED_CIRCLE,
-                        bar.value(self.no_count).build().unwrap()
-                    )
-                },
+                format!("Weighted Votes ({} voters)", self.voter_count),
+                render::vote_bars(&self.tally()),
                 false,
             );
+            if self.delegated_count > 0 {
+                embed = embed.field("Delegated Votes", self.delegated_count.to_string(), false);
+            }
 
             // row
-            {
-                if let Some(outcome) = self.invite_poll.outcome {
-                    embed = embed.field(
-                        "Outcome",
-                        match outcome {
-                            InvitePollOutcome::Allow => {
-                                [emojis::CHECK_MARK_BUTTON, " Allowed"].concat()
-                            }
-                            InvitePollOutcome::Deny => [emojis::NO_ENTRY, " Denied"].concat(),
-                        },
-                        true,
-                    );
-                } else {
-                    embed = embed.field("", "", true);
-                }
-
-                if let Some(message) = self.invite_poll.message.as_ref() {
-                    embed = embed.field(
-                        match self.invite_poll.outcome {
-                            Some(InvitePollOutcome::Allow) => "Invite",
-                            Some(InvitePollOutcome::Deny) => "Reason",
-                            None => "",
-                        },
-                        message,
-                        true,
-                    );
-                } else {
-                    embed = embed.field("", "", true);
-                }
+            let closed = self.invite_poll.outcome.is_some();
+            if self.invite_poll.vote_visibility.reveals_votes(closed) {
+                embed = embed.field(
+                    "Voters",
+                    render::vote_list(self.votes.iter().map(|v| (&v.user_id, v.vote))),
+                    false,
+                );
             }
 
-            embed
+            // row
+            if closed && !self.reasons.is_empty() {
+                embed = embed.field("Reasons", self.render_reasons(), false);
+            }
+
+            // row
+            render::outcome_row(
+                embed,
+                self.invite_poll.outcome.map(render_outcome),
+                self.invite_poll.message.as_deref().map(|message| {
+                    let label = match self.invite_poll.outcome {
+                        Some(InvitePollOutcome::Allow) => "Invite",
+                        Some(InvitePollOutcome::Deny | InvitePollOutcome::Cancelled) => "Reason",
+                        None => "",
+                    };
+                    (label, message)
+                }),
+            )
         }];
 
         let components = match self.invite_poll.outcome {
             Some(_) => Vec::new(),
-            None => vec![CreateActionRow::Buttons(vec![
-                CreateButton::new("democracy.invite-poll-vote.yes")
-                    .label("Yes")
-                    .style(ButtonStyle::Success),
-                CreateButton::new("democracy.invite-poll-vote.no")
-                    .label("No")
-                    .style(ButtonStyle::Danger),
-            ])],
+            None => vec![
+                render::vote_buttons("democracy.invite-poll-vote"),
+                CreateActionRow::Buttons(vec![CreateButton::new("democracy.invite-poll-cancel")
+                    .label("Cancel Poll")
+                    .style(ButtonStyle::Secondary)]),
+            ],
         };
 
         let mut res = MessageRenderer::default();
@@ -195,3 +284,21 @@ impl InvitePollWithVoteCount {
         Ok(res)
     }
 }
+
+#[async_trait]
+impl PollMessage for InvitePollWithVoteCount {
+    fn poll_id(&self) -> String {
+        self.invite_poll.id.to_string()
+    }
+
+    fn message_location(&self) -> (Option<&ChannelId>, Option<&MessageId>) {
+        (
+            self.invite_poll.channel_id.as_ref(),
+            self.invite_poll.message_id.as_ref(),
+        )
+    }
+
+    async fn render(&self, ctx: Context) -> Result<MessageRenderer, Error> {
+        self.create_renderer(ctx).await
+    }
+}
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serenity::model::prelude::Message;
+use sqlx::{postgres::types::PgInterval, Executor, PgExecutor, Postgres};
+
+use crate::{
+    error::Error,
+    util::serenity::{ChannelId, GuildId, MessageId, UserId},
+};
+
+use super::{KickPollId, KickPollOutcome};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct KickPoll {
+    pub id: KickPollId,
+    pub guild_id: GuildId,
+    pub initiator: UserId,
+    pub target: UserId,
+    pub channel_id: Option<ChannelId>,
+    pub message_id: Option<MessageId>,
+    pub outcome: Option<KickPollOutcome>,
+    pub message: Option<String>,
+    pub ends_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl KickPoll {
+    pub async fn create<'e, E>(
+        executor: E,
+        guild_id: &GuildId,
+        initiator: &UserId,
+        target: &UserId,
+        duration: &Duration,
+    ) -> Result<Self, Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        let duration = PgInterval::try_from(*duration).map_err(sqlx::Error::Decode)?;
+
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO kick_poll (guild_id, initiator, target, ends_at)
+                VALUES ($1, $2, $3, now() + $4)
+                RETURNING *;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(initiator)
+        .bind(target)
+        .bind(duration)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn update_message<'e, E>(
+        &mut self,
+        executor: E,
+        message: &Message,
+    ) -> Result<(), Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE kick_poll
+                SET channel_id = $2, message_id = $3
+                WHERE id = $1
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(ChannelId::from(message.channel_id))
+        .bind(MessageId::from(message.id))
+        .fetch_one(executor)
+        .await?;
+
+        *self = res;
+        Ok(())
+    }
+
+    pub async fn close<'c, E>(
+        &mut self,
+        executor: E,
+        outcome: KickPollOutcome,
+        message: Option<String>,
+    ) -> Result<(), Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                UPDATE kick_poll
+                SET outcome = $2, message = $3
+                WHERE id = $1
+                RETURNING *;
+            "#,
+        )
+        .bind(&self.id)
+        .bind(outcome)
+        .bind(message)
+        .fetch_one(executor)
+        .await?;
+
+        *self = res;
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+use crate::{error::Error, util::serenity::UserId};
+
+use super::{KickPollId, PollVote};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct KickPollVoteSubmission {
+    pub kick_poll_id: KickPollId,
+    pub user_id: UserId,
+    pub vote: PollVote,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl KickPollVoteSubmission {
+    pub async fn create_or_update<'c, E>(
+        executor: E,
+        kick_poll_id: &KickPollId,
+        user_id: &UserId,
+        vote: PollVote,
+    ) -> Result<Self, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO kick_poll_vote_submission (kick_poll_id, user_id, vote)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (kick_poll_id, user_id) DO UPDATE SET vote = EXCLUDED.vote
+                RETURNING *;
+            "#,
+        )
+        .bind(kick_poll_id)
+        .bind(user_id)
+        .bind(vote)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
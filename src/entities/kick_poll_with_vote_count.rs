@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use serenity::{builder::CreateEmbed, prelude::Context};
+use sqlx::{Executor, Postgres};
+
+use crate::{
+    error::Error,
+    util::{
+        colors, emojis,
+        serenity::{ChannelId, MessageId, MessageRenderer},
+    },
+};
+
+use super::{render, KickPoll, KickPollId, KickPollOutcome, PollMessage, VoteTally};
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct KickPollWithVoteCount {
+    #[sqlx(flatten)]
+    pub kick_poll: KickPoll,
+
+    pub yes_count: i64,
+    pub no_count: i64,
+    pub abstain_count: i64,
+}
+
+impl KickPollWithVoteCount {
+    pub async fn find_by_id<'c, E>(executor: E, id: &KickPollId) -> Result<Option<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM kick_poll_with_vote_count
+                WHERE id = $1;
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub async fn find_expired<'c, E>(executor: E) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM kick_poll_with_vote_count
+                WHERE outcome IS NULL AND ends_at <= now();
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    pub fn tally(&self) -> VoteTally {
+        VoteTally {
+            yes: self.yes_count,
+            no: self.no_count,
+            abstain: self.abstain_count,
+        }
+    }
+
+    pub async fn create_renderer(&self, ctx: Context) -> Result<MessageRenderer, Error> {
+        let user = self.kick_poll.target.to_user(&ctx.http).await?;
+
+        let embeds = vec![{
+            let mut embed = CreateEmbed::default();
+
+            embed = embed
+                .color(match self.kick_poll.outcome {
+                    Some(KickPollOutcome::Kick) => colors::DISCORD_RED,
+                    Some(KickPollOutcome::Keep) => colors::DISCORD_GREEN,
+                    None => colors::DISCORD_BLURPLE,
+                })
+                .title("Kick Poll")
+                .thumbnail(user.face());
+
+            // row
+            embed = render::summary_row(
+                embed,
+                &self.kick_poll.id,
+                ("User", &user.name),
+                self.kick_poll.outcome.is_some(),
+            );
+
+            // row
+            embed =
+                render::schedule_fields(embed, self.kick_poll.created_at, self.kick_poll.ends_at);
+
+            // row
+            embed = embed.field("Votes", render::vote_bars(&self.tally()), false);
+
+            // row
+            render::outcome_row(
+                embed,
+                self.kick_poll.outcome.map(|outcome| match outcome {
+                    KickPollOutcome::Kick => [emojis::NO_ENTRY, " Kicked"].concat(),
+                    KickPollOutcome::Keep => [emojis::CHECK_MARK_BUTTON, " Kept"].concat(),
+                }),
+                self.kick_poll.message.as_deref().map(|message| {
+                    let label = match self.kick_poll.outcome {
+                        Some(KickPollOutcome::Kick) => "Note",
+                        Some(KickPollOutcome::Keep) => "Reason",
+                        None => "",
+                    };
+                    (label, message)
+                }),
+            )
+        }];
+
+        let components = match self.kick_poll.outcome {
+            Some(_) => Vec::new(),
+            None => vec![render::vote_buttons("democracy.kick-poll-vote")],
+        };
+
+        let mut res = MessageRenderer::default();
+        res.set_components(components);
+        res.set_embeds(embeds);
+
+        Ok(res)
+    }
+}
+
+#[async_trait]
+impl PollMessage for KickPollWithVoteCount {
+    fn poll_id(&self) -> String {
+        self.kick_poll.id.to_string()
+    }
+
+    fn message_location(&self) -> (Option<&ChannelId>, Option<&MessageId>) {
+        (
+            self.kick_poll.channel_id.as_ref(),
+            self.kick_poll.message_id.as_ref(),
+        )
+    }
+
+    async fn render(&self, ctx: Context) -> Result<MessageRenderer, Error> {
+        self.create_renderer(ctx).await
+    }
+}
@@ -1,24 +1,181 @@
+mod audit_event;
+mod choice_poll;
+mod choice_poll_option;
+mod choice_poll_option_with_vote_count;
+mod choice_poll_ranking;
+mod choice_poll_vote_submission;
+mod choice_poll_with_vote_count;
+mod choice_result;
+mod community_poll;
+mod community_poll_vote_submission;
+mod community_poll_with_vote_count;
+mod decision_rule;
+mod delegation;
 mod guild;
+mod guild_vote_weight;
 mod invite_poll;
+mod invite_poll_message_refresh;
+mod invite_poll_nudge;
+mod invite_poll_reminder;
+mod invite_poll_vote_event;
 mod invite_poll_vote_submission;
 mod invite_poll_with_vote_count;
+mod kick_poll;
+mod kick_poll_vote_submission;
+mod kick_poll_with_vote_count;
+mod notification_preference;
+mod poll_id;
+mod render;
 
+pub use audit_event::*;
+pub use choice_poll::*;
+pub use choice_poll_option::*;
+pub use choice_poll_option_with_vote_count::*;
+pub use choice_poll_ranking::*;
+pub use choice_poll_vote_submission::*;
+pub use choice_poll_with_vote_count::*;
+pub use choice_result::*;
+pub use community_poll::*;
+pub use community_poll_vote_submission::*;
+pub use community_poll_with_vote_count::*;
+pub use decision_rule::*;
+pub use delegation::*;
 pub use guild::*;
+pub use guild_vote_weight::*;
 pub use invite_poll::*;
+pub use invite_poll_message_refresh::*;
+pub use invite_poll_nudge::*;
+pub use invite_poll_reminder::*;
+pub use invite_poll_vote_event::*;
 pub use invite_poll_vote_submission::*;
 pub use invite_poll_with_vote_count::*;
+pub use kick_poll::*;
+pub use kick_poll_vote_submission::*;
+pub use kick_poll_with_vote_count::*;
+pub use notification_preference::*;
+pub use poll_id::*;
+pub use render::{PollMessage, MAX_FIELD_LENGTH};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
 #[sqlx(type_name = "invite_poll_outcome", rename_all = "lowercase")]
 pub enum InvitePollOutcome {
     Allow,
     Deny,
+    Cancelled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "audit_event_kind", rename_all = "snake_case")]
+pub enum AuditEventKind {
+    ConfigChanged,
+    PollCreated,
+    PollCancelled,
+    PollForceClosed,
+    PollExtended,
+    PollReopened,
+    InviteGenerated,
+    DeliveryFailed,
+    MemberKicked,
+    KickFailed,
+}
+
+impl std::fmt::Display for AuditEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConfigChanged => write!(f, "Configuration Changed"),
+            Self::PollCreated => write!(f, "Poll Created"),
+            Self::PollCancelled => write!(f, "Poll Cancelled"),
+            Self::PollForceClosed => write!(f, "Poll Force Closed"),
+            Self::PollExtended => write!(f, "Poll Extended"),
+            Self::PollReopened => write!(f, "Poll Reopened"),
+            Self::InviteGenerated => write!(f, "Invite Generated"),
+            Self::DeliveryFailed => write!(f, "Delivery Failed"),
+            Self::MemberKicked => write!(f, "Member Kicked"),
+            Self::KickFailed => write!(f, "Kick Failed"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "kick_poll_outcome", rename_all = "lowercase")]
+pub enum KickPollOutcome {
+    Kick,
+    Keep,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "community_poll_outcome", rename_all = "lowercase")]
+pub enum CommunityPollOutcome {
+    Passed,
+    Rejected,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "choice_poll_outcome", rename_all = "snake_case")]
+pub enum ChoicePollOutcome {
+    Decided,
+    Tied,
+    NoVotes,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "choice_poll_mode", rename_all = "lowercase")]
+pub enum ChoicePollMode {
+    /// Each voter picks a single option.
+    Single,
+    /// Each voter ranks the options, the winner is determined through an instant-runoff.
+    Ranked,
+}
+
+/// Who can see how each member voted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type, strum::EnumString)]
+#[sqlx(type_name = "vote_visibility", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum VoteVisibility {
+    /// Only the vote counts are shown.
+    Secret,
+    /// The votes are listed once the poll is closed.
+    AfterClose,
+    /// The votes are listed while the poll is open.
+    Public,
+}
+
+impl VoteVisibility {
+    /// Whether the votes of a poll are revealed, depending on whether the poll is `closed`.
+    pub fn reveals_votes(&self, closed: bool) -> bool {
+        match self {
+            Self::Secret => false,
+            Self::AfterClose => closed,
+            Self::Public => true,
+        }
+    }
+}
+
+impl std::fmt::Display for VoteVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Secret => write!(f, "Secret"),
+            Self::AfterClose => write!(f, "Revealed After Close"),
+            Self::Public => write!(f, "Public"),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, sqlx::Type, strum::EnumString)]
-#[sqlx(type_name = "invite_poll_vote", rename_all = "lowercase")]
+#[sqlx(type_name = "poll_vote", rename_all = "lowercase")]
 #[strum(serialize_all = "snake_case")]
-pub enum InvitePollVote {
+pub enum PollVote {
     Yes,
     No,
+    Abstain,
+}
+
+impl std::fmt::Display for PollVote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Yes => write!(f, "Yes"),
+            Self::No => write!(f, "No"),
+            Self::Abstain => write!(f, "Abstain"),
+        }
+    }
 }
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+use crate::{
+    error::Error,
+    util::serenity::{GuildId, UserId},
+};
+
+/// The notifications a member of a guild opted into.
+#[derive(Debug, sqlx::FromRow)]
+pub struct NotificationPreference {
+    pub guild_id: GuildId,
+    pub user_id: UserId,
+    /// Whether the member is sent a direct message when an invite poll they have not voted on is
+    /// about to end.
+    pub vote_nudges: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl NotificationPreference {
+    pub async fn create_or_update<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+        user_id: &UserId,
+        vote_nudges: bool,
+    ) -> Result<Self, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                INSERT INTO notification_preference (guild_id, user_id, vote_nudges)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id, user_id) DO UPDATE SET
+                    vote_nudges = EXCLUDED.vote_nudges
+                RETURNING *;
+            "#,
+        )
+        .bind(guild_id)
+        .bind(user_id)
+        .bind(vote_nudges)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Finds the members of a guild who opted into vote nudges.
+    pub async fn find_vote_nudges_by_guild_id<'c, E>(
+        executor: E,
+        guild_id: &GuildId,
+    ) -> Result<Vec<Self>, Error>
+    where
+        E: Executor<'c, Database = Postgres>,
+    {
+        let res = sqlx::query_as::<_, Self>(
+            r#"
+                SELECT *
+                FROM notification_preference
+                WHERE guild_id = $1 AND vote_nudges;
+            "#,
+        )
+        .bind(guild_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,111 @@
+use std::{fmt::Display, str::FromStr};
+
+use base64::{display::Base64Display, Engine};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+static BASE64: base64::engine::GeneralPurpose = base64::engine::general_purpose::STANDARD_NO_PAD;
+
+macro_rules! poll_id {
+    ($id:ident) => {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, sqlx::Type)]
+        #[sqlx(transparent)]
+        pub struct $id(pub Uuid);
+
+        impl Display for $id {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                Base64Display::new(self.0.as_bytes(), &BASE64).fmt(f)
+            }
+        }
+
+        impl FromStr for $id {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let buf = BASE64
+                    .decode(s)
+                    .map_err(|err| Error::PollIdInvalid(s.to_owned(), Box::new(err)))?;
+
+                let id = Uuid::from_slice(&buf)
+                    .map_err(|err| Error::PollIdInvalid(s.to_owned(), Box::new(err)))?;
+
+                Ok(Self(id))
+            }
+        }
+    };
+}
+
+poll_id!(InvitePollId);
+poll_id!(KickPollId);
+poll_id!(CommunityPollId);
+poll_id!(ChoicePollId);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "poll_kind", rename_all = "lowercase")]
+pub enum PollKind {
+    Invite,
+    Kick,
+    Community,
+    Choice,
+}
+
+impl Display for PollKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invite => write!(f, "Invite Poll"),
+            Self::Kick => write!(f, "Kick Poll"),
+            Self::Community => write!(f, "Community Poll"),
+            Self::Choice => write!(f, "Choice Poll"),
+        }
+    }
+}
+
+/// The id of a poll of any kind.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PollRef {
+    Invite(InvitePollId),
+    Kick(KickPollId),
+    Community(CommunityPollId),
+    Choice(ChoicePollId),
+}
+
+impl PollRef {
+    pub fn from_parts(kind: PollKind, id: Uuid) -> Self {
+        match kind {
+            PollKind::Invite => Self::Invite(InvitePollId(id)),
+            PollKind::Kick => Self::Kick(KickPollId(id)),
+            PollKind::Community => Self::Community(CommunityPollId(id)),
+            PollKind::Choice => Self::Choice(ChoicePollId(id)),
+        }
+    }
+
+    pub fn kind(&self) -> PollKind {
+        match self {
+            Self::Invite(_) => PollKind::Invite,
+            Self::Kick(_) => PollKind::Kick,
+            Self::Community(_) => PollKind::Community,
+            Self::Choice(_) => PollKind::Choice,
+        }
+    }
+
+    pub fn uuid(&self) -> &Uuid {
+        match self {
+            Self::Invite(id) => &id.0,
+            Self::Kick(id) => &id.0,
+            Self::Community(id) => &id.0,
+            Self::Choice(id) => &id.0,
+        }
+    }
+}
+
+impl Display for PollRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invite(id) => id.fmt(f),
+            Self::Kick(id) => id.fmt(f),
+            Self::Community(id) => id.fmt(f),
+            Self::Choice(id) => id.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serenity::{
+    all::ButtonStyle,
+    builder::{CreateActionRow, CreateButton, CreateEmbed},
+    prelude::Context,
+};
+
+use crate::{
+    action::POLL_ID_FIELD_NAME,
+    error::Error,
+    util::{
+        emojis,
+        serenity::{ChannelId, MessageId, MessageRenderer, UserId},
+        DiscordTimestamp, DiscordTimestampStyle, ProgressBar,
+    },
+};
+
+use super::{PollVote, VoteTally};
+
+/// The maximum length of the value of an embed field.
+pub const MAX_FIELD_LENGTH: usize = 1024;
+
+/// A poll sent as a message which is re-rendered whenever the poll changes.
+#[async_trait]
+pub trait PollMessage: Sync {
+    /// The id of the poll, as shown in its message.
+    fn poll_id(&self) -> String;
+
+    /// The channel and the id of the message of the poll, if it was sent.
+    fn message_location(&self) -> (Option<&ChannelId>, Option<&MessageId>);
+
+    async fn render(&self, ctx: Context) -> Result<MessageRenderer, Error>;
+}
+
+/// Adds the row identifying a poll: its id, the member it is about and whether it is still open.
+pub fn summary_row(
+    embed: CreateEmbed,
+    poll_id: &impl Display,
+    (user_label, user_name): (&str, &str),
+    closed: bool,
+) -> CreateEmbed {
+    embed
+        .field(POLL_ID_FIELD_NAME, format!("`{}`", poll_id), true)
+        .field(user_label, user_name, true)
+        .field("Status", if closed { "Closed" } else { "Open" }, true)
+}
+
+/// Adds the creation and end times of a poll, leaving room for one more field in the row.
+pub fn schedule_fields(
+    embed: CreateEmbed,
+    created_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> CreateEmbed {
+    let ends_at = DiscordTimestamp::new(ends_at, DiscordTimestampStyle::FullShort);
+
+    embed
+        .field(
+            "Created At",
+            DiscordTimestamp::new(created_at, DiscordTimestampStyle::FullShort),
+            true,
+        )
+        .field(
+            "Ends At",
+            format!(
+                "{} ({})",
+                ends_at,
+                ends_at.with_style(DiscordTimestampStyle::Relative)
+            ),
+            true,
+        )
+}
+
+/// Adds the row with the outcome of a closed poll and its labelled message, empty fields keep the
+/// layout of the embed the same while the poll is open.
+pub fn outcome_row(
+    embed: CreateEmbed,
+    outcome: Option<String>,
+    message: Option<(&str, &str)>,
+) -> CreateEmbed {
+    let (message_label, message) = message.unwrap_or_default();
+
+    embed
+        .field(
+            if outcome.is_some() { "Outcome" } else { "" },
+            outcome.unwrap_or_default(),
+            true,
+        )
+        .field(message_label, message, true)
+}
+
+/// Renders the yes/no/abstain buttons of a poll whose votes are handled by `action_id`.
+pub fn vote_buttons(action_id: &str) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("{}.yes", action_id))
+            .label("Yes")
+            .style(ButtonStyle::Success),
+        CreateButton::new(format!("{}.no", action_id))
+            .label("No")
+            .style(ButtonStyle::Danger),
+        CreateButton::new(format!("{}.abstain", action_id))
+            .label("Abstain")
+            .style(ButtonStyle::Secondary),
+    ])
+}
+
+/// Renders the mentions of the voters grouped by vote kind, truncated to fit an embed field.
+pub fn vote_list<'a>(votes: impl IntoIterator<Item = (&'a UserId, PollVote)>) -> String {
+    let mut yes = Vec::new();
+    let mut no = Vec::new();
+    let mut abstain = Vec::new();
+    for (user_id, vote) in votes {
+        match vote {
+            PollVote::Yes => yes.push(user_id),
+            PollVote::No => no.push(user_id),
+            PollVote::Abstain => abstain.push(user_id),
+        }
+    }
+
+    let mut res = String::new();
+    for (emoji, user_ids) in [
+        (emojis::LARGE_GREEN_CIRCLE, yes),
+        (emojis::LARGE_RED_CIRCLE, no),
+        (emojis::WHITE_CIRCLE, abstain),
+    ] {
+        if user_ids.is_empty() {
+            continue;
+        }
+
+        let mut line = emoji.to_string();
+        for (i, user_id) in user_ids.iter().enumerate() {
+            let mention = format!(" {}", user_id);
+            let more = format!(" and {} more", user_ids.len() - i);
+
+            // keep enough room for the "and n more" suffix
+            if res.len() + line.len() + mention.len() + more.len() + 1 > MAX_FIELD_LENGTH {
+                line.push_str(&more);
+                break;
+            }
+            line.push_str(&mention);
+        }
+
+        if !res.is_empty() {
+            res.push('\n');
+        }
+        res.push_str(&line);
+    }
+
+    if res.is_empty() {
+        res.push_str("No votes yet");
+    }
+
+    res
+}
+
+/// Renders the reasons given by the voters, one per line, prefixed by the mention of the voter when
+/// their votes are revealed, truncated to fit an embed field.
+pub fn reason_list<'a>(reasons: impl IntoIterator<Item = (Option<&'a UserId>, &'a str)>) -> String {
+    let reasons = reasons.into_iter().collect::<Vec<_>>();
+
+    let mut res = String::new();
+    for (i, (user_id, reason)) in reasons.iter().enumerate() {
+        let reason = reason.replace('\n', " ");
+        let line = match user_id {
+            Some(user_id) => format!("- {}: {}", user_id, reason),
+            None => format!("- {}", reason),
+        };
+        let more = format!("and {} more", reasons.len() - i);
+
+        // keep enough room for the "and n more" suffix
+        if res.len() + line.len() + more.len() + 2 > MAX_FIELD_LENGTH {
+            res.push_str(&more);
+            break;
+        }
+        res.push_str(&line);
+        res.push('\n');
+    }
+
+    res.trim_end().to_string()
+}
+
+/// Joins `items` with `separator`, replacing the ones which would not fit in `max_length` with an
+/// "and n more" suffix.
+pub fn truncated_join(items: &[String], separator: &str, max_length: usize) -> String {
+    let mut res = String::new();
+    for (i, item) in items.iter().enumerate() {
+        let item = if i == 0 {
+            item.clone()
+        } else {
+            [separator, item].concat()
+        };
+        let more = format!(" and {} more", items.len() - i);
+
+        // keep enough room for the "and n more" suffix, unless this is the last item
+        let needed = if i + 1 == items.len() { 0 } else { more.len() };
+        if res.len() + item.len() + needed > max_length {
+            res.push_str(&more);
+            break;
+        }
+        res.push_str(&item);
+    }
+
+    res
+}
+
+/// Renders one progress bar per vote kind.
+pub fn vote_bars(tally: &VoteTally) -> String {
+    let mut bar = ProgressBar::builder();
+    bar.max(tally.total() + tally.abstain)
+        .with_count(true)
+        .with_percentage(true);
+
+    format!(
+        "{} {}\n{} {}\n{} {}",
+        emojis::LARGE_GREEN_CIRCLE,
+        bar.value(tally.yes).build().unwrap(),
+        emojis::LARGE_RED_CIRCLE,
+        bar.value(tally.no).build().unwrap(),
+        emojis::WHITE_CIRCLE,
+        bar.value(tally.abstain).build().unwrap()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vote_list() {
+        let alice: UserId = "1".parse().unwrap();
+        let bob: UserId = "2".parse().unwrap();
+
+        assert_eq!(vote_list([]), "No votes yet");
+        assert_eq!(
+            vote_list([(&alice, PollVote::Yes), (&bob, PollVote::No)]),
+            format!(
+                "{} <@1>\n{} <@2>",
+                emojis::LARGE_GREEN_CIRCLE,
+                emojis::LARGE_RED_CIRCLE
+            )
+        );
+    }
+
+    #[test]
+    fn test_reason_list() {
+        let alice: UserId = "1".parse().unwrap();
+
+        assert_eq!(reason_list([]), "");
+        assert_eq!(
+            reason_list([(Some(&alice), "not\nyet"), (None, "too soon")]),
+            "- <@1>: not yet\n- too soon"
+        );
+
+        let reasons = vec!["a".repeat(100); 20];
+        let res = reason_list(reasons.iter().map(|reason| (None, reason.as_str())));
+        assert!(res.len() <= MAX_FIELD_LENGTH);
+        assert!(res.ends_with("more"));
+    }
+
+    #[test]
+    fn test_truncated_join() {
+        let items = vec!["a".to_owned(), "b".to_owned(), "c".to_owned()];
+
+        assert_eq!(truncated_join(&[], ", ", 10), "");
+        assert_eq!(truncated_join(&items, ", ", 100), "a, b, c");
+        assert_eq!(truncated_join(&items, ", ", 14), "a and 2 more");
+
+        let items = vec!["a".repeat(100); 20];
+        let res = truncated_join(&items, ", ", MAX_FIELD_LENGTH);
+        assert!(res.len() <= MAX_FIELD_LENGTH);
+        assert!(res.ends_with("more"));
+    }
+
+    #[test]
+    fn test_vote_list_truncated() {
+        let users: Vec<UserId> = (0..100)
+            .map(|i| {
+                format!("{}", 100_000_000_000_000_000u64 + i)
+                    .parse()
+                    .unwrap()
+            })
+            .collect();
+
+        let res = vote_list(users.iter().map(|user_id| (user_id, PollVote::Yes)));
+        assert!(res.len() <= MAX_FIELD_LENGTH);
+        assert!(res.ends_with("more"));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
     action::ParseActionError,
-    entities::InvitePollId,
-    util::serenity::{GuildId, UserId},
+    entities::{ChoicePollId, CommunityPollId, InvitePollId, KickPollId, PollVote},
+    util::{
+        serenity::{GuildId, UserId},
+        DiscordTimestamp,
+    },
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -9,8 +12,29 @@ pub enum Error {
     #[error("could not find an invite poll with id `{0}`")]
     InvitePollNotFound(InvitePollId),
 
+    #[error("the invite poll with id `{0}` is already closed")]
+    InvitePollClosed(InvitePollId),
+
+    #[error("the invite poll with id `{0}` is still open")]
+    InvitePollOpen(InvitePollId),
+
+    #[error("the invite poll with id `{0}` was allowed and its invite already sent")]
+    CannotReopenAllowedInvitePoll(InvitePollId),
+
+    #[error("could not find a kick poll with id `{0}`")]
+    KickPollNotFound(KickPollId),
+
+    #[error("could not find a poll with id `{0}`")]
+    CommunityPollNotFound(CommunityPollId),
+
+    #[error("could not find a poll with id `{0}`")]
+    ChoicePollNotFound(ChoicePollId),
+
+    #[error("the poll with id `{0}` is already closed")]
+    ChoicePollClosed(ChoicePollId),
+
     #[error("value `{0}` is not a valid poll id: {1}")]
-    InvitePollIdInvalid(String, Box<dyn std::error::Error + Send + Sync>),
+    PollIdInvalid(String, Box<dyn std::error::Error + Send + Sync>),
 
     #[error("could not find a guild with id `{0:?}`")]
     GuildNotFound(GuildId),
@@ -18,6 +42,48 @@ pub enum Error {
     #[error("user '{0}' is already a member")]
     CannotInviteMember(UserId),
 
+    #[error("an invite poll for user '{0}' is already open: {1}")]
+    DuplicateInvitePoll(UserId, String),
+
+    #[error("user '{0}' was recently denied or withdrawn and can be proposed again {1}")]
+    InviteeOnCooldown(UserId, DiscordTimestamp),
+
+    #[error("you have opened too many invite polls, the limit resets {0}")]
+    InvitePollRateLimited(DiscordTimestamp),
+
+    #[error("you do not have a role allowed to open invite polls")]
+    MissingProposerRole,
+
+    #[error("you do not have a role allowed to vote on invite polls")]
+    MissingVoterRole,
+
+    #[error("new members cannot vote on invite polls yet, you will be able to vote {0}")]
+    InsufficientTenure(DiscordTimestamp),
+
+    #[error("user '{0}' is not a member")]
+    CannotKickNonMember(UserId),
+
+    #[error("user '{0}' owns the guild")]
+    CannotKickOwner(UserId),
+
+    #[error("you cannot vote on a poll to kick yourself")]
+    CannotVoteOnOwnKick,
+
+    #[error("user '{0}' is neither the inviter nor a manager")]
+    CannotCancelInvitePoll(UserId),
+
+    #[error("cannot delegate your vote to yourself")]
+    CannotDelegateToSelf,
+
+    #[error("cannot delegate your vote to user '{0}' because they are not a member")]
+    CannotDelegateToNonMember(UserId),
+
+    #[error("cannot delegate your vote to user '{0}' because they are a bot")]
+    CannotDelegateToBot(UserId),
+
+    #[error("you already voted {0} on this poll and changing votes is not allowed")]
+    VoteAlreadyCast(PollVote),
+
     #[error(transparent)]
     ParseActionError(#[from] ParseActionError),
 
@@ -44,12 +110,43 @@ pub enum Error {
 }
 
 impl Error {
+    /// Whether the error was caused by a query violating the `constraint`.
+    pub fn is_constraint_violation(&self, constraint: &str) -> bool {
+        match self {
+            Error::DatabaseError(sqlx::Error::Database(err)) => {
+                err.constraint() == Some(constraint)
+            }
+            _ => false,
+        }
+    }
+
     pub fn is_client_error(&self) -> bool {
         match self {
             Error::InvitePollNotFound(_) => true,
-            Error::InvitePollIdInvalid(_, _) => true,
+            Error::InvitePollClosed(_) => true,
+            Error::InvitePollOpen(_) => true,
+            Error::CannotReopenAllowedInvitePoll(_) => true,
+            Error::KickPollNotFound(_) => true,
+            Error::CommunityPollNotFound(_) => true,
+            Error::ChoicePollNotFound(_) => true,
+            Error::ChoicePollClosed(_) => true,
+            Error::PollIdInvalid(_, _) => true,
             Error::GuildNotFound(_) => true,
             Error::CannotInviteMember(_) => true,
+            Error::DuplicateInvitePoll(_, _) => true,
+            Error::InviteeOnCooldown(_, _) => true,
+            Error::InvitePollRateLimited(_) => true,
+            Error::MissingProposerRole => true,
+            Error::MissingVoterRole => true,
+            Error::InsufficientTenure(_) => true,
+            Error::CannotKickNonMember(_) => true,
+            Error::CannotKickOwner(_) => true,
+            Error::CannotVoteOnOwnKick => true,
+            Error::CannotCancelInvitePoll(_) => true,
+            Error::CannotDelegateToSelf => true,
+            Error::CannotDelegateToNonMember(_) => true,
+            Error::CannotDelegateToBot(_) => true,
+            Error::VoteAlreadyCast(_) => true,
             Error::ParseActionError(err) => err.is_client_error(),
             Error::ConfigError(_) => false,
             Error::DatabaseError(_) => false,
@@ -31,5 +31,7 @@ pub mod emojis {
     pub static LARGE_YELLOW_CIRCLE: &str = "\u{1F7E1}";
     pub static NO_ENTRY: &str = "⛔";
     pub static PROHIBITED: &str = "🚫";
+    pub static TROPHY: &str = "\u{1F3C6}";
     pub static WARNING: &str = "⚠️";
+    pub static WHITE_CIRCLE: &str = "\u{26AA}";
 }
@@ -1,4 +1,4 @@
-use std::{fmt::Display, ops::Deref, str::FromStr};
+use std::{fmt::Display, num::NonZeroU64, ops::Deref, str::FromStr};
 
 use async_trait::async_trait;
 use serenity::{
@@ -7,14 +7,16 @@ use serenity::{
         CreateMessage, EditMessage,
     },
     http::{CacheHttp, Http, StatusCode},
-    model::prelude::{Interaction, PartialGuild},
+    model::prelude::{Interaction, Member, PartialGuild},
 };
-use sqlx::Postgres;
+use sqlx::{postgres::PgHasArrayType, Postgres};
 
 pub trait ErrorExt {
     fn as_http_error(&self) -> Option<&serenity::http::HttpError>;
 
     fn is_cannot_send_messages_to_this_user_error(&self) -> bool;
+
+    fn is_not_found_error(&self) -> bool;
 }
 
 impl ErrorExt for serenity::Error {
@@ -31,6 +33,12 @@ impl ErrorExt for serenity::Error {
             .map(|err| err.error.code == 50007)
             .unwrap_or(false)
     }
+
+    fn is_not_found_error(&self) -> bool {
+        self.as_http_error()
+            .map(|err| err.status_code() == Some(StatusCode::NOT_FOUND))
+            .unwrap_or(false)
+    }
 }
 
 pub trait HttpErrorExt {
@@ -53,6 +61,19 @@ pub trait GuildExt {
         cache_http: impl CacheHttp,
         user_id: impl Into<serenity::model::id::UserId> + Send,
     ) -> Result<bool, serenity::Error>;
+
+    /// Fetches a member of the guild, `None` if the user is not a member.
+    async fn find_member(
+        &self,
+        cache_http: impl CacheHttp,
+        user_id: impl Into<serenity::model::id::UserId> + Send,
+    ) -> Result<Option<Member>, serenity::Error>;
+
+    /// Fetches all the members of the guild that are not bots.
+    async fn human_members(
+        &self,
+        http: impl AsRef<Http> + Send,
+    ) -> Result<Vec<Member>, serenity::Error>;
 }
 
 #[async_trait]
@@ -62,15 +83,43 @@ impl GuildExt for PartialGuild {
         cache_http: impl CacheHttp,
         user_id: impl Into<serenity::model::id::UserId> + Send,
     ) -> Result<bool, serenity::Error> {
+        let res = self.find_member(cache_http, user_id).await?;
+        Ok(res.is_some())
+    }
+
+    async fn find_member(
+        &self,
+        cache_http: impl CacheHttp,
+        user_id: impl Into<serenity::model::id::UserId> + Send,
+    ) -> Result<Option<Member>, serenity::Error> {
         let res = self.member(cache_http, user_id.into()).await;
         match res {
-            Ok(_) => Ok(true),
+            Ok(member) => Ok(Some(member)),
             Err(ref err) if matches!(err, serenity::Error::Http(http) if http.status_code() == Some(StatusCode::NOT_FOUND)) => {
-                Ok(false)
+                Ok(None)
             }
             Err(err) => Err(err),
         }
     }
+
+    async fn human_members(
+        &self,
+        http: impl AsRef<Http> + Send,
+    ) -> Result<Vec<Member>, serenity::Error> {
+        let mut res = Vec::new();
+        let mut after: Option<serenity::model::id::UserId> = None;
+        loop {
+            let page = self.members(http.as_ref(), None, after).await?;
+            if page.is_empty() {
+                break;
+            }
+
+            after = page.last().map(|m| m.user.id);
+            res.extend(page.into_iter().filter(|m| !m.user.bot));
+        }
+
+        Ok(res)
+    }
 }
 
 #[async_trait]
@@ -180,10 +229,11 @@ macro_rules! wrap_discord_id {
         }
 
         impl FromStr for $id {
-            type Err = <u64 as FromStr>::Err;
+            type Err = <NonZeroU64 as FromStr>::Err;
 
+            // serenity panics on zero ids
             fn from_str(s: &str) -> Result<Self, Self::Err> {
-                Ok(Self(s.parse::<u64>()?.into()))
+                Ok(Self(s.parse::<NonZeroU64>()?.into()))
             }
         }
 
@@ -197,6 +247,16 @@ macro_rules! wrap_discord_id {
             }
         }
 
+        impl PgHasArrayType for $id {
+            fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                <String as PgHasArrayType>::array_type_info()
+            }
+
+            fn array_compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                <String as PgHasArrayType>::array_compatible(ty)
+            }
+        }
+
         impl<'r> sqlx::Decode<'r, Postgres> for $id {
             fn decode(
                 value: <Postgres as sqlx::database::HasValueRef<'r>>::ValueRef,
@@ -222,9 +282,22 @@ wrap_discord_id!(GuildId);
 wrap_discord_id!(UserId);
 wrap_discord_id!(ChannelId);
 wrap_discord_id!(MessageId);
+wrap_discord_id!(RoleId);
 
 impl Display for UserId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "<@{}>", self.0.get())
     }
 }
+
+impl Display for ChannelId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<#{}>", self.0.get())
+    }
+}
+
+impl Display for RoleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<@&{}>", self.0.get())
+    }
+}